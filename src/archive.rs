@@ -1,4 +1,4 @@
-use crate::{enums::ObjectVersionUE5, Error, ObjectVersion, Result};
+use crate::{enums::ObjectVersionUE5, CustomVersion, Error, Guid, ObjectVersion, Result};
 use binread::BinReaderExt;
 use num_traits::FromPrimitive;
 use std::io::{Read, Seek};
@@ -22,6 +22,8 @@ pub struct Archive<R> {
     /// The licensee serialization version used when saving this asset (C++ name: `FileVersionLicenseeUE4`)
     pub file_licensee_version: i32,
     pub legacy_version: i32,
+    /// The custom versions this asset was saved with, populated while parsing the [`crate::AssetHeader`] (C++ name: `CustomVersionContainer`)
+    pub custom_versions: Vec<CustomVersion>,
     // Copied from the [`AssetHeader::package_flags`] to be accessible during serialization
     pub with_editoronly_data: bool,
 }
@@ -78,6 +80,7 @@ where
             file_version_ue5,
             file_licensee_version,
             legacy_version,
+            custom_versions: Vec::new(),
             with_editoronly_data: false,
         })
     }
@@ -95,6 +98,21 @@ where
     }
 }
 
+impl<R> Archive<R> {
+    /// Replace the reader of this archive with `f(reader)`, keeping the versions that were read from the package summary
+    pub(crate) fn map_reader<T>(self, f: impl FnOnce(R) -> Result<T>) -> Result<Archive<T>> {
+        Ok(Archive {
+            reader: f(self.reader)?,
+            file_version: self.file_version,
+            file_version_ue5: self.file_version_ue5,
+            file_licensee_version: self.file_licensee_version,
+            legacy_version: self.legacy_version,
+            custom_versions: self.custom_versions,
+            with_editoronly_data: self.with_editoronly_data,
+        })
+    }
+}
+
 pub trait SerializedFlags {
     fn serialized_with_editoronly_data(&self) -> bool;
 }
@@ -129,6 +147,28 @@ impl<R> SerializedObjectVersion<ObjectVersionUE5> for Archive<R> {
     }
 }
 
+/// Look up the custom versions (see [`CustomVersion`]) an asset was serialized with, analogous to how
+/// `SerializedObjectVersion` looks up engine object versions.
+pub trait SerializedCustomVersion {
+    /// The version the custom version identified by `guid` was serialized with, or `None` if it was not used
+    fn custom_version(&self, guid: Guid) -> Option<i32>;
+
+    /// True if the custom version identified by `guid` was serialized with at least `version`
+    fn serialized_with_custom_version(&self, guid: Guid, version: i32) -> bool {
+        self.custom_version(guid)
+            .is_some_and(|serialized_version| serialized_version >= version)
+    }
+}
+
+impl<R> SerializedCustomVersion for Archive<R> {
+    fn custom_version(&self, guid: Guid) -> Option<i32> {
+        self.custom_versions
+            .iter()
+            .find(|custom_version| custom_version.guid == guid)
+            .map(|custom_version| custom_version.version)
+    }
+}
+
 impl<R> Read for Archive<R>
 where
     R: Read,
@@ -0,0 +1,511 @@
+use crate::{AssetHeader, Error};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::File,
+    io::{BufReader, Read, Seek},
+    path::{Path, PathBuf},
+};
+
+/// File extensions used by uassets
+pub const UASSET_EXTENSIONS: [&str; 2] = ["uasset", "umap"];
+
+/// Check if a path has one of the [`UASSET_EXTENSIONS`]
+pub fn is_uasset<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref();
+    if let Some(extension) = path.extension() {
+        let extension = extension.to_string_lossy();
+        UASSET_EXTENSIONS.contains(&extension.as_ref())
+    } else {
+        false
+    }
+}
+
+/// Recursively find the assets in `directory`, following symbolic links and skipping hidden files and directories. Directories
+/// that can't be read are added to `failures`.
+fn find_uassets(
+    directory: &Path,
+    visited_directories: &mut HashSet<PathBuf>,
+    asset_paths: &mut Vec<PathBuf>,
+    failures: &mut Vec<(PathBuf, Error)>,
+) {
+    // Symbolic links can lead back to a directory we're already in
+    match directory.canonicalize() {
+        Ok(canonical_directory) => {
+            if !visited_directories.insert(canonical_directory) {
+                return;
+            }
+        }
+        Err(error) => {
+            failures.push((directory.to_path_buf(), error.into()));
+            return;
+        }
+    }
+
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(error) => {
+            failures.push((directory.to_path_buf(), error.into()));
+            return;
+        }
+    };
+    let mut paths = Vec::new();
+    for entry in entries {
+        match entry {
+            Ok(entry) => paths.push(entry.path()),
+            Err(error) => failures.push((directory.to_path_buf(), error.into())),
+        }
+    }
+    paths.sort();
+
+    for path in paths {
+        let is_hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            find_uassets(&path, visited_directories, asset_paths, failures);
+        } else if is_uasset(&path) && path.is_file() {
+            asset_paths.push(path);
+        }
+    }
+}
+
+/// Compare two names case insensitively, like the engine compares package names
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a == b || a.to_lowercase() == b.to_lowercase()
+}
+
+/// Find the entry in `directory` whose name (without its extension, for files) matches `name` ignoring case, and that satisfies
+/// `predicate`
+fn find_entry(directory: &Path, name: &str, predicate: impl Fn(&Path) -> bool) -> Option<PathBuf> {
+    std::fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            let entry_name = if path.is_dir() {
+                path.file_name()
+            } else {
+                path.file_stem()
+            };
+            entry_name
+                .and_then(|entry_name| entry_name.to_str())
+                .is_some_and(|entry_name| eq_ignore_case(entry_name, name))
+                && predicate(path)
+        })
+}
+
+/// How one package refers to another
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ReferenceKind {
+    /// The package is imported, and will be loaded along with the referencing package
+    Hard,
+    /// The package is only referenced through a soft object path, and is loaded on demand
+    Soft,
+}
+
+/// A reference from or to a package in an [`AssetIndex`]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PackageReference {
+    /// Name of the package on the other end of the reference, e.g. `/Game/Maps/MyMap`
+    pub package_name: String,
+    pub kind: ReferenceKind,
+}
+
+/// Maps package names like `/Game/Maps/MyMap` to assets on disk and back, for the content directories of a project and its plugins
+#[derive(Clone, Debug, Default)]
+pub struct MountPoints {
+    /// Pairs of a mount point (e.g. `/Game/`) and the canonicalized directory it is mounted from
+    mounts: Vec<(String, PathBuf)>,
+}
+
+impl MountPoints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mount `directory` at `mount_point`, e.g. a project's `Content` directory at `/Game/` or a plugin's `Content` directory at
+    /// `/PluginName/`. Fails if the directory can't be found.
+    pub fn mount<P: AsRef<Path>>(&mut self, mount_point: &str, directory: P) -> crate::Result<()> {
+        let mount_point = format!("{}/", mount_point.trim_end_matches('/'));
+        self.mounts
+            .push((mount_point, directory.as_ref().canonicalize()?));
+        Ok(())
+    }
+
+    /// Iterate over the mount points (with a trailing `/`) and the directories they are mounted from
+    pub fn mounts(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.mounts
+            .iter()
+            .map(|(mount_point, directory)| (mount_point.as_str(), directory.as_path()))
+    }
+
+    /// Check if a package exists on disk, returns `None` if the package isn't under any of our mount points (e.g. `/Script/` or
+    /// `/Engine/`). Package names are case insensitive, so this finds the package even if it's cased differently on disk.
+    pub fn package_exists(&self, package_name: &str) -> Option<bool> {
+        let (mount_point, directory) = self.mounts.iter().find(|(mount_point, _)| {
+            package_name
+                .get(..mount_point.len())
+                .is_some_and(|prefix| eq_ignore_case(prefix, mount_point))
+        })?;
+        let relative_name = &package_name[mount_point.len()..];
+
+        let package_path = directory.join(relative_name);
+        if UASSET_EXTENSIONS
+            .iter()
+            .any(|extension| package_path.with_extension(extension).is_file())
+        {
+            return Some(true);
+        }
+
+        // Look for each directory and the asset itself ignoring case, as the filesystem may be case sensitive
+        let Some((directory_names, asset_name)) = relative_name.rsplit_once('/') else {
+            return Some(
+                find_entry(directory, relative_name, |path| {
+                    path.is_file() && is_uasset(path)
+                })
+                .is_some(),
+            );
+        };
+        let mut package_directory = directory.clone();
+        for directory_name in directory_names.split('/') {
+            match find_entry(&package_directory, directory_name, Path::is_dir) {
+                Some(path) => package_directory = path,
+                None => return Some(false),
+            }
+        }
+        Some(
+            find_entry(&package_directory, asset_name, |path| {
+                path.is_file() && is_uasset(path)
+            })
+            .is_some(),
+        )
+    }
+
+    /// Determine the package name of an asset on disk, returns `None` if it isn't under any of our mount points. The asset itself
+    /// doesn't need to exist, but its directory does.
+    pub fn package_name<P: AsRef<Path>>(&self, asset_path: P) -> Option<String> {
+        let asset_path = asset_path.as_ref();
+        let directory = match asset_path.parent()? {
+            directory if directory.as_os_str().is_empty() => Path::new(".").canonicalize().ok()?,
+            directory => directory.canonicalize().ok()?,
+        };
+        let asset_path = directory.join(asset_path.file_stem()?);
+
+        self.mounts.iter().find_map(|(mount_point, directory)| {
+            let relative_path = asset_path.strip_prefix(directory).ok()?;
+            let components: Vec<_> = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect();
+            Some(format!("{}{}", mount_point, components.join("/")))
+        })
+    }
+
+    /// Find the references from `header` (the package `package_name`) to packages under our mount points that don't exist on disk,
+    /// or that are listed in `deleted_packages`. References to packages that aren't under any mount point (e.g. `/Script/` or
+    /// `/Engine/`) are assumed to exist.
+    pub fn missing_references<R>(
+        &self,
+        package_name: &str,
+        header: &mut AssetHeader<R>,
+        deleted_packages: &HashSet<String>,
+    ) -> crate::Result<Vec<PackageReference>>
+    where
+        R: Read + Seek,
+    {
+        let mut references = header.package_references(package_name)?;
+        references.retain(|reference| {
+            deleted_packages.contains(&reference.package_name)
+                || self.package_exists(&reference.package_name) == Some(false)
+        });
+        Ok(references)
+    }
+}
+
+/// The key for `path` in [`AssetIndex::package_name_for_path`], so that different paths to the same file find the same package
+fn path_key(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// A package that's been added to an [`AssetIndex`]
+#[derive(Debug)]
+pub struct IndexedPackage {
+    /// Location of the asset on disk
+    pub path: PathBuf,
+    /// Packages referenced by this package. Packages that are both imported and soft referenced are only listed as
+    /// [`ReferenceKind::Hard`], and references from the package to itself are left out.
+    pub dependencies: Vec<PackageReference>,
+}
+
+/// An index of the packages in one or more content directories, that can answer which packages a package references (its
+/// dependencies) and which packages reference it (its referencers).
+#[derive(Debug, Default)]
+pub struct AssetIndex {
+    packages: BTreeMap<String, IndexedPackage>,
+    referencers: BTreeMap<String, Vec<PackageReference>>,
+    /// The name of the package indexed from each path
+    package_names: HashMap<PathBuf, String>,
+}
+
+impl AssetIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index every asset in the directories of `mount_points`. Assets that can't be read and directories that can't be listed are
+    /// skipped, and returned along with the reason they failed.
+    pub fn scan(mount_points: &MountPoints) -> (Self, Vec<(PathBuf, Error)>) {
+        let mut visited_directories = HashSet::new();
+        let mut asset_paths = Vec::new();
+        let mut failures = Vec::new();
+        for (_, directory) in mount_points.mounts() {
+            find_uassets(
+                directory,
+                &mut visited_directories,
+                &mut asset_paths,
+                &mut failures,
+            );
+        }
+
+        let mut index = Self::new();
+        failures.extend(index.add_assets(mount_points, asset_paths));
+        (index, failures)
+    }
+
+    /// Index the assets in `asset_paths` (e.g. every asset in the directories of `mount_points`), naming them by where they are in
+    /// `mount_points`. Assets that aren't under any mount point are skipped, while assets that can't be read are skipped and
+    /// returned along with the reason they failed.
+    pub fn add_assets<I>(
+        &mut self,
+        mount_points: &MountPoints,
+        asset_paths: I,
+    ) -> Vec<(PathBuf, Error)>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<Path>,
+    {
+        let mut failures = Vec::new();
+        for asset_path in asset_paths {
+            let asset_path = asset_path.as_ref();
+            let Some(package_name) = mount_points.package_name(asset_path) else {
+                continue;
+            };
+
+            if let Err(error) = self.add_asset(package_name, asset_path) {
+                failures.push((asset_path.to_path_buf(), error));
+            }
+        }
+
+        failures
+    }
+
+    /// Parse the asset at `asset_path` and add it to the index as `package_name`
+    pub fn add_asset<P: AsRef<Path>>(
+        &mut self,
+        package_name: String,
+        asset_path: P,
+    ) -> crate::Result<()> {
+        let asset_path = asset_path.as_ref();
+        let mut header = AssetHeader::new(BufReader::new(File::open(asset_path)?))?;
+        let dependencies = header.package_references(&package_name)?;
+        self.add_package(
+            package_name,
+            IndexedPackage {
+                path: asset_path.to_path_buf(),
+                dependencies,
+            },
+        );
+        Ok(())
+    }
+
+    /// Add an already parsed package to the index, replacing any previous package with the same name
+    pub fn add_package(&mut self, package_name: String, package: IndexedPackage) {
+        self.remove_package(&package_name);
+
+        for dependency in &package.dependencies {
+            self.referencers
+                .entry(dependency.package_name.clone())
+                .or_default()
+                .push(PackageReference {
+                    package_name: package_name.clone(),
+                    kind: dependency.kind,
+                });
+        }
+        self.package_names
+            .insert(path_key(&package.path), package_name.clone());
+        self.packages.insert(package_name, package);
+    }
+
+    /// Remove a package from the index, returning it if it was present
+    pub fn remove_package(&mut self, package_name: &str) -> Option<IndexedPackage> {
+        let package = self.packages.remove(package_name)?;
+        if self
+            .package_names
+            .remove(&path_key(&package.path))
+            .is_none()
+        {
+            // The asset may have been deleted since it was indexed, so its path doesn't canonicalize to the same key anymore
+            self.package_names
+                .retain(|_, indexed_package_name| indexed_package_name != package_name);
+        }
+        for dependency in &package.dependencies {
+            if let Some(referencers) = self.referencers.get_mut(&dependency.package_name) {
+                referencers.retain(|referencer| referencer.package_name != package_name);
+            }
+        }
+        Some(package)
+    }
+
+    /// Iterate over the names and contents of every package in the index, sorted by name
+    pub fn packages(&self) -> impl Iterator<Item = (&str, &IndexedPackage)> {
+        self.packages
+            .iter()
+            .map(|(package_name, package)| (package_name.as_str(), package))
+    }
+
+    /// Look up a package by name, e.g. `/Game/Maps/MyMap`
+    pub fn package(&self, package_name: &str) -> Option<&IndexedPackage> {
+        self.packages.get(package_name)
+    }
+
+    /// Look up the name of the package that was indexed from `asset_path`, which doesn't have to be the same relative or absolute
+    /// path the package was indexed with
+    pub fn package_name_for_path<P: AsRef<Path>>(&self, asset_path: P) -> Option<&str> {
+        self.package_names
+            .get(&path_key(asset_path.as_ref()))
+            .map(|package_name| package_name.as_str())
+    }
+
+    /// The packages referenced by `package_name`, which is empty if the package isn't in the index
+    pub fn dependencies(&self, package_name: &str) -> &[PackageReference] {
+        self.packages
+            .get(package_name)
+            .map(|package| package.dependencies.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The packages in the index that reference `package_name`. The package itself doesn't need to be in the index.
+    pub fn referencers(&self, package_name: &str) -> &[PackageReference] {
+        self.referencers
+            .get(package_name)
+            .map(|referencers| referencers.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Find the groups of packages in the index that depend on each other in a cycle (i.e. the strongly connected components of the
+    /// dependency graph). Each cycle is sorted by package name, and the cycles are sorted by their first package. Soft references are
+    /// only followed if `include_soft_references` is set.
+    pub fn find_cycles(&self, include_soft_references: bool) -> Vec<Vec<String>> {
+        // Iterative version of Tarjan's strongly connected components algorithm, to avoid overflowing the stack on large projects
+        struct NodeState {
+            index: usize,
+            low_link: usize,
+            on_stack: bool,
+        }
+
+        let follows = |dependency: &&PackageReference| {
+            include_soft_references || dependency.kind == ReferenceKind::Hard
+        };
+
+        // A package being visited, with its dependencies (collected on the first visit) and how far we've gotten through them
+        struct Frame<'a> {
+            package_name: &'a str,
+            dependencies: Option<Vec<&'a str>>,
+            next_dependency: usize,
+        }
+
+        let mut states: BTreeMap<&str, NodeState> = BTreeMap::new();
+        let mut stack: Vec<&str> = Vec::new();
+        let mut cycles = Vec::new();
+
+        for root in self.packages.keys() {
+            if states.contains_key(root.as_str()) {
+                continue;
+            }
+
+            let mut call_stack = vec![Frame {
+                package_name: root.as_str(),
+                dependencies: None,
+                next_dependency: 0,
+            }];
+            while let Some(frame) = call_stack.last_mut() {
+                let package_name = frame.package_name;
+                let dependencies = frame.dependencies.get_or_insert_with(|| {
+                    let index = states.len();
+                    states.insert(
+                        package_name,
+                        NodeState {
+                            index,
+                            low_link: index,
+                            on_stack: true,
+                        },
+                    );
+                    stack.push(package_name);
+
+                    self.dependencies(package_name)
+                        .iter()
+                        .filter(follows)
+                        .map(|dependency| dependency.package_name.as_str())
+                        .filter(|dependency_name| self.packages.contains_key(*dependency_name))
+                        .collect()
+                });
+
+                if let Some(&dependency_name) = dependencies.get(frame.next_dependency) {
+                    frame.next_dependency += 1;
+                    match states.get(dependency_name) {
+                        None => call_stack.push(Frame {
+                            package_name: dependency_name,
+                            dependencies: None,
+                            next_dependency: 0,
+                        }),
+                        Some(dependency_state) if dependency_state.on_stack => {
+                            let dependency_index = dependency_state.index;
+                            let state = states.get_mut(package_name).unwrap();
+                            state.low_link = state.low_link.min(dependency_index);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+                call_stack.pop();
+
+                // We've visited all the dependencies, so propagate our low link to the package that depends on us
+                let state = &states[package_name];
+                let (index, low_link) = (state.index, state.low_link);
+                if let Some(parent) = call_stack.last() {
+                    let parent_state = states.get_mut(parent.package_name).unwrap();
+                    parent_state.low_link = parent_state.low_link.min(low_link);
+                }
+
+                if index == low_link {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        states.get_mut(member).unwrap().on_stack = false;
+                        component.push(member.to_string());
+                        if member == package_name {
+                            break;
+                        }
+                    }
+
+                    let references_itself = self
+                        .dependencies(package_name)
+                        .iter()
+                        .filter(follows)
+                        .any(|dependency| dependency.package_name == package_name);
+                    if component.len() > 1 || references_itself {
+                        component.sort();
+                        cycles.push(component);
+                    }
+                }
+            }
+        }
+
+        cycles.sort();
+        cycles
+    }
+}
@@ -0,0 +1,432 @@
+use crate::{Error, ObjectReference, Result, enums::impl_flags_display};
+use binread::BinReaderExt;
+use bitflags::bitflags;
+use flate2::read::ZlibDecoder;
+use std::{
+    io::{self, Read, Seek, SeekFrom, Take},
+    path::{Path, PathBuf},
+};
+
+/// Tag that starts the header of data written by `FArchive::SerializeCompressed`, same as the tag at the start of a package
+const PACKAGE_FILE_TAG: i64 = 0x9E2A83C1;
+/// Chunk size used by `FArchive::SerializeCompressed` before the chunk size was stored in the header
+const LOADING_COMPRESSION_CHUNK_SIZE: i64 = 128 * 1024;
+/// Versions of the data resource table (C++ name: `FObjectDataResource::EVersion`)
+const DATA_RESOURCE_VERSION_INITIAL: u32 = 1;
+const DATA_RESOURCE_VERSION_ADDED_COOKED_INDEX: u32 = 2;
+
+bitflags! {
+    /// Maps to `EBulkDataFlags` in Engine/Source/Runtime/CoreUObject/Public/Serialization/BulkData.h.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    pub struct BulkDataFlags: u32 {
+        const PayloadAtEndOfFile = 0x00000001;
+        const SerializeCompressedZLIB = 0x00000002;
+        const ForceSingleElementSerialization = 0x00000004;
+        const SingleUse = 0x00000008;
+        const Unused = 0x00000020;
+        const ForceInlinePayload = 0x00000040;
+        const ForceStreamPayload = 0x00000080;
+        const PayloadInSeperateFile = 0x00000100;
+        const SerializeCompressedBitWindow = 0x00000200;
+        const Force_NOT_InlinePayload = 0x00000400;
+        const OptionalPayload = 0x00000800;
+        const MemoryMappedPayload = 0x00001000;
+        const Size64Bit = 0x00002000;
+        const DuplicateNonOptionalPayload = 0x00004000;
+        const BadDataVersion = 0x00008000;
+        const NoOffsetFixUp = 0x00010000;
+        const WorkspaceDomainPayload = 0x00020000;
+        const LazyLoadable = 0x00040000;
+        const AlwaysAllowDiscard = 0x10000000;
+        const HasAsyncReadPending = 0x20000000;
+        const DataIsMemoryMapped = 0x40000000;
+        const UsesIoDispatcher = 0x80000000;
+    }
+}
+
+impl_flags_display!(BulkDataFlags);
+
+bitflags! {
+    /// Maps to `EObjectDataResourceFlags` in Engine/Source/Runtime/CoreUObject/Public/UObject/ObjectResource.h.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    pub struct ObjectDataResourceFlags: u32 {
+        const Inline = 0x00000001;
+        const Streaming = 0x00000002;
+        const Optional = 0x00000004;
+        const Duplicated = 0x00000008;
+        const MemoryMapped = 0x00000010;
+        const DerivedDataReference = 0x00000020;
+    }
+}
+
+impl_flags_display!(ObjectDataResourceFlags);
+
+/// A file next to a package that holds bulk data payloads, instead of the package itself
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BulkDataFile {
+    /// Payloads that are streamed in on demand (`.ubulk`)
+    Bulk,
+    /// Payloads that are only available if the optional data has been installed (`.uptnl`)
+    Optional,
+    /// Payloads that are memory mapped rather than read (`.m.ubulk`)
+    MemoryMapped,
+}
+
+impl BulkDataFile {
+    /// Extension of the file, which replaces the extension of the package
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BulkDataFile::Bulk => "ubulk",
+            BulkDataFile::Optional => "uptnl",
+            BulkDataFile::MemoryMapped => "m.ubulk",
+        }
+    }
+}
+
+/// Where the payload of some bulk data is stored
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BulkDataLocation {
+    /// Right after the bulk data header, in the data of the export
+    Inline,
+    /// After the exports in the package, starting at `AssetHeader::bulk_data_start_offset`
+    EndOfFile,
+    /// In a file next to the package
+    SeparateFile(BulkDataFile),
+}
+
+/// A copy of an optional payload that's also stored as a regular payload, for platforms that don't install the optional data
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DuplicateBulkData {
+    pub flags: BulkDataFlags,
+    pub size_on_disk: i64,
+    pub offset_in_file: i64,
+}
+
+/// The header of an `FBulkData`, describing where its payload is and how it's stored. It's read from inside the data of an
+/// export with [`crate::AssetHeader::read_bulk_data_header`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BulkDataHeader {
+    /// (C++ name: `BulkDataFlags`)
+    pub flags: BulkDataFlags,
+    /// Number of elements in the payload, which are bytes for `FByteBulkData` (C++ name: `ElementCount`)
+    pub element_count: i64,
+    /// Size of the payload as it's stored, which is the compressed size if it's compressed (C++ name: `BulkDataSizeOnDisk`)
+    pub size_on_disk: i64,
+    /// Where the payload starts in the file it's stored in. For inline payloads this is right after the header, and for payloads at
+    /// the end of the package it includes `bulk_data_start_offset`. (C++ name: `BulkDataOffsetInFile`)
+    pub offset_in_file: i64,
+    /// The duplicate of an optional payload, if there is one
+    pub duplicate: Option<DuplicateBulkData>,
+}
+
+impl BulkDataHeader {
+    /// Read a header in the format used before `ObjectVersionUE5::DATA_RESOURCES`, where `bulk_data_start_offset` is the
+    /// [`crate::AssetHeader::bulk_data_start_offset`] of the package (see `FBulkData::Serialize`)
+    pub(crate) fn parse<R: Read + Seek>(
+        reader: &mut R,
+        bulk_data_start_offset: i64,
+    ) -> Result<Self> {
+        let flags = BulkDataFlags::from_bits_retain(reader.read_le()?);
+        let element_count = Self::read_size(reader, flags)?;
+        let size_on_disk = Self::read_size(reader, flags)?;
+        let mut offset_in_file: i64 = reader.read_le()?;
+        if !flags.contains(BulkDataFlags::NoOffsetFixUp) {
+            offset_in_file += bulk_data_start_offset;
+        }
+
+        let mut flags = flags;
+        if flags.contains(BulkDataFlags::BadDataVersion) {
+            let _dummy_value: u16 = reader.read_le()?;
+            flags.remove(BulkDataFlags::BadDataVersion);
+        }
+
+        let duplicate = if flags.contains(BulkDataFlags::DuplicateNonOptionalPayload) {
+            let duplicate_flags = BulkDataFlags::from_bits_retain(reader.read_le()?);
+            let duplicate_size_on_disk = Self::read_size(reader, duplicate_flags)?;
+            let duplicate_offset_in_file = reader.read_le()?;
+            Some(DuplicateBulkData {
+                flags: duplicate_flags,
+                size_on_disk: duplicate_size_on_disk,
+                offset_in_file: duplicate_offset_in_file,
+            })
+        } else {
+            None
+        };
+
+        let mut header = Self {
+            flags,
+            element_count,
+            size_on_disk,
+            offset_in_file,
+            duplicate,
+        };
+        if header.location() == BulkDataLocation::Inline {
+            header.offset_in_file = reader.stream_position()? as i64;
+        }
+        Ok(header)
+    }
+
+    /// Build the header of bulk data saved with `ObjectVersionUE5::DATA_RESOURCES` or later, which only serializes an index into the
+    /// data resource table. `inline_offset` is where the payload would start if it's inline, i.e. right after the index.
+    pub(crate) fn from_data_resource(
+        resource: &ObjectDataResource,
+        bulk_data_start_offset: i64,
+        inline_offset: i64,
+    ) -> Self {
+        let flags = resource.legacy_bulk_data_flags;
+        let duplicate = if flags.contains(BulkDataFlags::DuplicateNonOptionalPayload) {
+            Some(DuplicateBulkData {
+                flags: flags
+                    - (BulkDataFlags::OptionalPayload | BulkDataFlags::DuplicateNonOptionalPayload),
+                size_on_disk: resource.serial_size,
+                offset_in_file: resource.duplicate_serial_offset,
+            })
+        } else {
+            None
+        };
+
+        let mut header = Self {
+            flags,
+            element_count: resource.raw_size,
+            size_on_disk: resource.serial_size,
+            offset_in_file: resource.serial_offset,
+            duplicate,
+        };
+        match header.location() {
+            BulkDataLocation::Inline => header.offset_in_file = inline_offset,
+            BulkDataLocation::EndOfFile | BulkDataLocation::SeparateFile(_) => {
+                if !flags.contains(BulkDataFlags::NoOffsetFixUp) {
+                    header.offset_in_file += bulk_data_start_offset;
+                }
+            }
+        }
+        header
+    }
+
+    /// Sizes are 64-bit if `BULKDATA_Size64Bit` is set, and 32-bit otherwise (see `SerializeBulkDataSizeInt`)
+    fn read_size<R: Read + Seek>(reader: &mut R, flags: BulkDataFlags) -> Result<i64> {
+        Ok(if flags.contains(BulkDataFlags::Size64Bit) {
+            reader.read_le()?
+        } else {
+            reader.read_le::<i32>()? as i64
+        })
+    }
+
+    /// Determine where the payload is stored from the flags
+    pub fn location(&self) -> BulkDataLocation {
+        if self.flags.contains(BulkDataFlags::PayloadInSeperateFile) {
+            if self.flags.contains(BulkDataFlags::OptionalPayload) {
+                BulkDataLocation::SeparateFile(BulkDataFile::Optional)
+            } else if self.flags.contains(BulkDataFlags::MemoryMappedPayload) {
+                BulkDataLocation::SeparateFile(BulkDataFile::MemoryMapped)
+            } else {
+                BulkDataLocation::SeparateFile(BulkDataFile::Bulk)
+            }
+        } else if self.flags.contains(BulkDataFlags::PayloadAtEndOfFile) {
+            BulkDataLocation::EndOfFile
+        } else {
+            BulkDataLocation::Inline
+        }
+    }
+
+    /// True if the payload is compressed with zlib, in the format written by `FArchive::SerializeCompressed`
+    pub fn is_compressed(&self) -> bool {
+        self.flags.contains(BulkDataFlags::SerializeCompressedZLIB)
+    }
+
+    /// The path of the file that holds the payload, for a package at `package_path`. This is `None` if the payload is stored in the
+    /// package itself.
+    pub fn separate_file_path<P: AsRef<Path>>(&self, package_path: P) -> Option<PathBuf> {
+        match self.location() {
+            BulkDataLocation::SeparateFile(file) => {
+                Some(package_path.as_ref().with_extension(file.extension()))
+            }
+            BulkDataLocation::Inline | BulkDataLocation::EndOfFile => None,
+        }
+    }
+
+    /// Read the payload from `stream`, which should be the package for [`BulkDataLocation::Inline`] and
+    /// [`BulkDataLocation::EndOfFile`], or the file at [`BulkDataHeader::separate_file_path`] otherwise. Compressed payloads are
+    /// decompressed as they're read.
+    pub fn reader<S: Read + Seek>(&self, mut stream: S) -> Result<BulkDataReader<S>> {
+        let offset = u64::try_from(self.offset_in_file).map_err(|_error| {
+            Error::InvalidBulkData(format!("invalid payload offset {}", self.offset_in_file))
+        })?;
+        let size_on_disk = u64::try_from(self.size_on_disk).map_err(|_error| {
+            Error::InvalidBulkData(format!("invalid payload size {}", self.size_on_disk))
+        })?;
+        stream.seek(SeekFrom::Start(offset))?;
+
+        if !self.is_compressed() {
+            return Ok(BulkDataReader::Uncompressed(stream.take(size_on_disk)));
+        }
+
+        let compressed_chunk_sizes = read_compression_header(&mut stream, Error::InvalidBulkData)?;
+        Ok(BulkDataReader::Compressed(ZlibChunkReader::new(
+            stream,
+            compressed_chunk_sizes,
+        )))
+    }
+}
+
+/// An entry in the data resource table of a package saved with `ObjectVersionUE5::DATA_RESOURCES` or later, which describes a
+/// bulk data payload of one of its objects. Read with [`crate::AssetHeader::data_resources`]. (C++ name: `FObjectDataResource`)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ObjectDataResource {
+    /// (C++ name: `Flags`)
+    pub flags: ObjectDataResourceFlags,
+    /// Which cooked variant of the payload this is, only serialized by newer versions of the table (C++ name: `CookedIndex`)
+    pub cooked_index: u8,
+    /// Where the payload starts in the file it's stored in, see [`BulkDataHeader::offset_in_file`] (C++ name: `SerialOffset`)
+    pub serial_offset: i64,
+    /// Where the duplicate of an optional payload starts, or -1 if there isn't one (C++ name: `DuplicateSerialOffset`)
+    pub duplicate_serial_offset: i64,
+    /// Size of the payload as it's stored, which is the compressed size if it's compressed (C++ name: `SerialSize`)
+    pub serial_size: i64,
+    /// Size of the payload after decompressing it (C++ name: `RawSize`)
+    pub raw_size: i64,
+    /// The object that owns the payload (C++ name: `OuterIndex`)
+    pub outer: ObjectReference,
+    /// The flags of the `FBulkData` this payload was saved from (C++ name: `LegacyBulkDataFlags`)
+    pub legacy_bulk_data_flags: BulkDataFlags,
+}
+
+impl ObjectDataResource {
+    /// Read the table at the current position of `reader` (see `FObjectDataResource::Serialize`)
+    pub(crate) fn parse_table<R: Read + Seek>(reader: &mut R) -> Result<Vec<Self>> {
+        let version: u32 = reader.read_le()?;
+        if !(DATA_RESOURCE_VERSION_INITIAL..=DATA_RESOURCE_VERSION_ADDED_COOKED_INDEX)
+            .contains(&version)
+        {
+            return Err(Error::InvalidBulkData(format!(
+                "unsupported data resource table version {}",
+                version
+            )));
+        }
+
+        let count: i32 = reader.read_le()?;
+        let count = usize::try_from(count).map_err(|_error| {
+            Error::InvalidBulkData(format!("invalid data resource count {}", count))
+        })?;
+        let mut resources = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            let flags = ObjectDataResourceFlags::from_bits_retain(reader.read_le()?);
+            let cooked_index = if version >= DATA_RESOURCE_VERSION_ADDED_COOKED_INDEX {
+                reader.read_le()?
+            } else {
+                0
+            };
+            resources.push(Self {
+                flags,
+                cooked_index,
+                serial_offset: reader.read_le()?,
+                duplicate_serial_offset: reader.read_le()?,
+                serial_size: reader.read_le()?,
+                raw_size: reader.read_le()?,
+                outer: ObjectReference::from(reader.read_le::<i32>()?),
+                legacy_bulk_data_flags: BulkDataFlags::from_bits_retain(reader.read_le()?),
+            });
+        }
+        Ok(resources)
+    }
+}
+
+/// Read the header of data written by `FArchive::SerializeCompressed`, returning the compressed size of each of the zlib streams that
+/// follow it. Invalid headers are reported with `invalid_data`.
+pub(crate) fn read_compression_header<S: Read + Seek>(
+    stream: &mut S,
+    invalid_data: fn(String) -> Error,
+) -> Result<Vec<u64>> {
+    let package_file_tag: i64 = stream.read_le()?;
+    let chunk_size: i64 = stream.read_le()?;
+    if package_file_tag != PACKAGE_FILE_TAG {
+        return Err(invalid_data(format!(
+            "unsupported compressed data tag {:#x}",
+            package_file_tag
+        )));
+    }
+    let chunk_size = if chunk_size == PACKAGE_FILE_TAG {
+        LOADING_COMPRESSION_CHUNK_SIZE
+    } else {
+        chunk_size
+    };
+
+    let _total_compressed_size: i64 = stream.read_le()?;
+    let total_uncompressed_size: i64 = stream.read_le()?;
+    if chunk_size <= 0 || total_uncompressed_size < 0 {
+        return Err(invalid_data(format!(
+            "invalid compressed data with chunk size {} and uncompressed size {}",
+            chunk_size, total_uncompressed_size
+        )));
+    }
+
+    let chunk_count = (total_uncompressed_size + chunk_size - 1) / chunk_size;
+    let mut compressed_chunk_sizes = Vec::with_capacity(chunk_count.min(1024) as usize);
+    for _ in 0..chunk_count {
+        let compressed_size: i64 = stream.read_le()?;
+        let _uncompressed_size: i64 = stream.read_le()?;
+        compressed_chunk_sizes.push(u64::try_from(compressed_size).map_err(|_error| {
+            invalid_data(format!("invalid compressed chunk size {}", compressed_size))
+        })?);
+    }
+    Ok(compressed_chunk_sizes)
+}
+
+/// Reads the payload of some bulk data, see [`BulkDataHeader::reader`]
+pub enum BulkDataReader<S> {
+    Uncompressed(Take<S>),
+    Compressed(ZlibChunkReader<S>),
+}
+
+impl<S: Read> Read for BulkDataReader<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            BulkDataReader::Uncompressed(reader) => reader.read(buf),
+            BulkDataReader::Compressed(reader) => reader.read(buf),
+        }
+    }
+}
+
+/// Decompresses a sequence of zlib streams that follow each other in `stream`, given the compressed size of each
+pub struct ZlibChunkReader<S> {
+    stream: Option<S>,
+    decoder: Option<ZlibDecoder<Take<S>>>,
+    compressed_chunk_sizes: std::vec::IntoIter<u64>,
+}
+
+impl<S: Read> ZlibChunkReader<S> {
+    pub fn new(stream: S, compressed_chunk_sizes: Vec<u64>) -> Self {
+        Self {
+            stream: Some(stream),
+            decoder: None,
+            compressed_chunk_sizes: compressed_chunk_sizes.into_iter(),
+        }
+    }
+}
+
+impl<S: Read> Read for ZlibChunkReader<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(decoder) = &mut self.decoder {
+                let bytes_read = decoder.read(buf)?;
+                if bytes_read > 0 || buf.is_empty() {
+                    return Ok(bytes_read);
+                }
+
+                // We've finished this chunk, so skip anything the decoder didn't need and start on the next one
+                if let Some(decoder) = self.decoder.take() {
+                    let mut chunk = decoder.into_inner();
+                    io::copy(&mut chunk, &mut io::sink())?;
+                    self.stream = Some(chunk.into_inner());
+                }
+            }
+
+            let Some(compressed_size) = self.compressed_chunk_sizes.next() else {
+                return Ok(0);
+            };
+            if let Some(stream) = self.stream.take() {
+                self.decoder = Some(ZlibDecoder::new(stream.take(compressed_size)));
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{
+    CompressedChunk, CompressionFlags, Error, Result,
+    bulk_data::{ZlibChunkReader, read_compression_header},
+};
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// A reader that presents a package saved with compressed chunks as the uncompressed package, which is what the offsets in it refer
+/// to. The package summary at the start isn't compressed so it's read as is, while each chunk is decompressed in full when it's first
+/// read from. Typically used through [`crate::AssetHeader::new_compressed`].
+#[derive(Debug)]
+pub struct CompressedPackageReader<R> {
+    reader: R,
+    /// The compressed chunks, sorted by where they are in the uncompressed package
+    compressed_chunks: Vec<CompressedChunk>,
+    /// Size of the uncompressed package
+    len: u64,
+    /// Our position in the uncompressed package
+    position: u64,
+    /// Last known position of the underlying reader, so we only seek it when needed (which would discard its buffer)
+    reader_position: Option<u64>,
+    /// Index of the last chunk we decompressed, and its data
+    decompressed_chunk_index: Option<usize>,
+    decompressed_chunk: Vec<u8>,
+}
+
+impl<R> CompressedPackageReader<R>
+where
+    R: Seek + Read,
+{
+    /// Create a reader for the package in `reader`, with the compression settings and chunks from its package summary. Only zlib
+    /// compression is supported.
+    pub fn new(
+        mut reader: R,
+        compression_flags: CompressionFlags,
+        mut compressed_chunks: Vec<CompressedChunk>,
+    ) -> Result<Self> {
+        if compressed_chunks.is_empty() {
+            let len = reader.seek(SeekFrom::End(0))?;
+            return Ok(Self::with_chunks(reader, compressed_chunks, len));
+        }
+
+        // The other flags are hints for the compressor, which don't matter for decompression
+        let method = compression_flags
+            & (CompressionFlags::ZLIB | CompressionFlags::GZIP | CompressionFlags::Custom);
+        if method != CompressionFlags::ZLIB {
+            return Err(Error::InvalidCompressedPackage(format!(
+                "unsupported compression flags {}",
+                compression_flags
+            )));
+        }
+        if let Some(chunk) = compressed_chunks.iter().find(|chunk| {
+            chunk.uncompressed_offset < 0
+                || chunk.uncompressed_size < 0
+                || chunk.compressed_offset < 0
+        }) {
+            return Err(Error::InvalidCompressedPackage(format!(
+                "invalid compressed chunk {:?}",
+                chunk
+            )));
+        }
+
+        compressed_chunks.sort_by_key(|chunk| chunk.uncompressed_offset);
+        let len = compressed_chunks
+            .iter()
+            .map(|chunk| chunk.uncompressed_offset as u64 + chunk.uncompressed_size as u64)
+            .max()
+            .unwrap_or(0);
+        Ok(Self::with_chunks(reader, compressed_chunks, len))
+    }
+
+    fn with_chunks(reader: R, compressed_chunks: Vec<CompressedChunk>, len: u64) -> Self {
+        Self {
+            reader,
+            compressed_chunks,
+            len,
+            position: 0,
+            reader_position: None,
+            decompressed_chunk_index: None,
+            decompressed_chunk: Vec::new(),
+        }
+    }
+
+    /// The compressed chunks of the package, sorted by where they are in the uncompressed package
+    pub fn compressed_chunks(&self) -> &[CompressedChunk] {
+        &self.compressed_chunks
+    }
+
+    /// Read from the start of the package, which isn't compressed
+    fn read_uncompressed(&mut self, buf: &mut [u8], end: u64) -> io::Result<usize> {
+        if self.reader_position != Some(self.position) {
+            self.reader.seek(SeekFrom::Start(self.position))?;
+        }
+        let buf_len = buf
+            .len()
+            .min((end - self.position).try_into().unwrap_or(usize::MAX));
+        let bytes_read = self.reader.read(&mut buf[..buf_len])?;
+        self.reader_position = Some(self.position + bytes_read as u64);
+        Ok(bytes_read)
+    }
+
+    /// Decompress a chunk into `decompressed_chunk`, unless it's already there
+    fn decompress_chunk(&mut self, chunk_index: usize) -> Result<()> {
+        if self.decompressed_chunk_index == Some(chunk_index) {
+            return Ok(());
+        }
+
+        // Each chunk is written with `FArchive::SerializeCompressed`
+        let chunk = self.compressed_chunks[chunk_index];
+        self.decompressed_chunk_index = None;
+        self.reader_position = None;
+        self.reader
+            .seek(SeekFrom::Start(chunk.compressed_offset as u64))?;
+        let compressed_block_sizes =
+            read_compression_header(&mut self.reader, Error::InvalidCompressedPackage)?;
+
+        let uncompressed_size = chunk.uncompressed_size as usize;
+        self.decompressed_chunk.clear();
+        ZlibChunkReader::new(&mut self.reader, compressed_block_sizes)
+            .take(uncompressed_size as u64)
+            .read_to_end(&mut self.decompressed_chunk)?;
+        if self.decompressed_chunk.len() != uncompressed_size {
+            return Err(Error::InvalidCompressedPackage(format!(
+                "chunk at offset {} decompressed to {} bytes instead of {}",
+                chunk.uncompressed_offset,
+                self.decompressed_chunk.len(),
+                uncompressed_size
+            )));
+        }
+        self.decompressed_chunk_index = Some(chunk_index);
+        Ok(())
+    }
+}
+
+impl<R> Read for CompressedPackageReader<R>
+where
+    R: Seek + Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(first_chunk) = self.compressed_chunks.first() else {
+            let bytes_read = self.read_uncompressed(buf, u64::MAX)?;
+            self.position += bytes_read as u64;
+            return Ok(bytes_read);
+        };
+
+        let first_chunk_offset = first_chunk.uncompressed_offset as u64;
+        let bytes_read = if self.position < first_chunk_offset {
+            self.read_uncompressed(buf, first_chunk_offset)?
+        } else {
+            let position = self.position;
+            let chunk_index = self.compressed_chunks.iter().position(|chunk| {
+                let start = chunk.uncompressed_offset as u64;
+                (start..start + chunk.uncompressed_size as u64).contains(&position)
+            });
+            // Anything past the end of the chunks (or between them) isn't part of the package
+            let Some(chunk_index) = chunk_index else {
+                return Ok(0);
+            };
+
+            let chunk_offset = self.compressed_chunks[chunk_index].uncompressed_offset as u64;
+            self.decompress_chunk(chunk_index)
+                .map_err(|error| match error {
+                    Error::Io(error) => error,
+                    error => io::Error::new(io::ErrorKind::InvalidData, error.to_string()),
+                })?;
+            let data = &self.decompressed_chunk[(position - chunk_offset) as usize..];
+            let bytes_read = buf.len().min(data.len());
+            buf[..bytes_read].copy_from_slice(&data[..bytes_read]);
+            bytes_read
+        };
+
+        self.position += bytes_read as u64;
+        Ok(bytes_read)
+    }
+}
+
+impl<R> Seek for CompressedPackageReader<R>
+where
+    R: Seek + Read,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(position) => (position, 0),
+            SeekFrom::Current(offset) => (self.position, offset),
+            SeekFrom::End(offset) => (self.len, offset),
+        };
+
+        self.position = base.checked_add_signed(offset).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
@@ -1,6 +1,21 @@
+use crate::Guid;
+use bitflags::bitflags;
 use num_derive::FromPrimitive;
 use std::fmt;
 
+/// Implements [`fmt::Display`] for a bitflags type by listing the names of the set flags separated by `|`, with any unknown
+/// bits at the end in hex (e.g. `Public|Standalone|0x100`)
+macro_rules! impl_flags_display {
+    ($flags:ty) => {
+        impl std::fmt::Display for $flags {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                bitflags::parser::to_writer(self, f)
+            }
+        }
+    };
+}
+pub(crate) use impl_flags_display;
+
 /// Maps to `EUnrealEngineObjectUE4Version` in Engine/Source/Runtime/Core/Public/UObject/ObjectVersion.h -- this tracks
 /// the various versions to the core serialized object format.
 #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
@@ -350,80 +365,181 @@ impl fmt::Display for ObjectVersionUE5 {
     }
 }
 
-/// Maps to `EPackageFlags` in Engine/Source/Runtime/CoreUObject/Public/UObject/ObjectMacros.h. The `UnusedN` entries
-/// exist for completeness, presumably those entries were used in the past.
-#[derive(Debug, FromPrimitive)]
-#[repr(u32)]
-pub enum PackageFlags {
-    None = 0x00000000,
-    NewlyCreated = 0x00000001,
-    ClientOptional = 0x00000002,
-    ServerSideOnly = 0x00000004,
-    CompiledIn = 0x00000010,
-    ForDiffing = 0x00000020,
-    EditorOnly = 0x00000040,
-    Developer = 0x00000080,
-    UncookedOnly = 0x00000100,
-    Cooked = 0x00000200,
-    ContainsNoAsset = 0x00000400,
-    Unused1 = 0x00000800,
-    Unused2 = 0x00001000,
-    UnversionedProperties = 0x00002000,
-    ContainsMapData = 0x00004000,
-    Unused3 = 0x00008000,
-    Compiling = 0x00010000,
-    ContainsMap = 0x00020000,
-    RequiresLocalizationGather = 0x00040000,
-    Unused4 = 0x00080000,
-    PlayInEditor = 0x00100000,
-    ContainsScript = 0x00200000,
-    DisallowExport = 0x00400000,
-    Unused5 = 0x00800000,
-    Unused6 = 0x01000000,
-    Unused7 = 0x02000000,
-    Unused8 = 0x04000000,
-    Unused9 = 0x08000000,
-    DynamicImports = 0x10000000,
-    RuntimeGenerated = 0x20000000,
-    ReloadingForCooker = 0x40000000,
-    FilterEditorOnly = 0x80000000,
+/// Some of the custom versions registered by the engine (using `FCustomVersionRegistration`) that are commonly referenced
+/// when serializing assets. Use [`KnownCustomVersion::guid`] to look them up with [`crate::SerializedCustomVersion`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KnownCustomVersion {
+    /// Maps to `FCoreObjectVersion` in Engine/Source/Runtime/Core/Public/UObject/CoreObjectVersion.h
+    CoreObjectVersion,
+    /// Maps to `FEditorObjectVersion` in Engine/Source/Runtime/Core/Public/UObject/EditorObjectVersion.h
+    EditorObjectVersion,
+    /// Maps to `FFrameworkObjectVersion` in Engine/Source/Runtime/Core/Public/UObject/FrameworkObjectVersion.h
+    FrameworkObjectVersion,
+    /// Maps to `FReleaseObjectVersion` in Engine/Source/Runtime/Core/Public/UObject/ReleaseObjectVersion.h
+    ReleaseObjectVersion,
+    /// Maps to `FBlueprintsObjectVersion` in Engine/Source/Runtime/Core/Public/UObject/BlueprintsObjectVersion.h
+    BlueprintsObjectVersion,
+    /// Maps to `FFortniteMainBranchObjectVersion` in Engine/Source/Runtime/Core/Public/UObject/FortniteMainBranchObjectVersion.h
+    FortniteMainBranchObjectVersion,
+    /// Maps to `FUE5MainStreamObjectVersion` in Engine/Source/Runtime/Core/Public/UObject/UE5MainStreamObjectVersion.h
+    UE5MainStreamObjectVersion,
+    /// Maps to `FUE5ReleaseStreamObjectVersion` in Engine/Source/Runtime/Core/Public/UObject/UE5ReleaseStreamObjectVersion.h
+    UE5ReleaseStreamObjectVersion,
+}
+
+impl KnownCustomVersion {
+    pub const ALL: [Self; 8] = [
+        Self::CoreObjectVersion,
+        Self::EditorObjectVersion,
+        Self::FrameworkObjectVersion,
+        Self::ReleaseObjectVersion,
+        Self::BlueprintsObjectVersion,
+        Self::FortniteMainBranchObjectVersion,
+        Self::UE5MainStreamObjectVersion,
+        Self::UE5ReleaseStreamObjectVersion,
+    ];
+
+    /// The key used to identify this custom version in an asset (C++ name: `GUID`)
+    pub const fn guid(self) -> Guid {
+        match self {
+            Self::CoreObjectVersion => Guid::new(0x375EC13C, 0x06E448FB, 0xB50084F0, 0x262A717E),
+            Self::EditorObjectVersion => Guid::new(0xE4B068ED, 0xF49442E9, 0xA231DA0B, 0x2E46BB41),
+            Self::FrameworkObjectVersion => {
+                Guid::new(0xCFFC743F, 0x43B04480, 0x939114DF, 0x171D2073)
+            }
+            Self::ReleaseObjectVersion => Guid::new(0x9C54D522, 0xA8264FBE, 0x94210746, 0x61B482D0),
+            Self::BlueprintsObjectVersion => {
+                Guid::new(0xB0D832E4, 0x1F894F0D, 0xACCF7EB7, 0x36FD4AA2)
+            }
+            Self::FortniteMainBranchObjectVersion => {
+                Guid::new(0x601D1886, 0xAC644F84, 0xAA16D3DE, 0x0DEAC7D6)
+            }
+            Self::UE5MainStreamObjectVersion => {
+                Guid::new(0x697DD581, 0xE64F41AB, 0xAA4A51EC, 0xBEB7B628)
+            }
+            Self::UE5ReleaseStreamObjectVersion => {
+                Guid::new(0xD89B5E42, 0x24BD4D46, 0x8412ACA8, 0xDF641779)
+            }
+        }
+    }
+
+    /// The name of the C++ type declaring this custom version
+    pub const fn friendly_name(self) -> &'static str {
+        match self {
+            Self::CoreObjectVersion => "FCoreObjectVersion",
+            Self::EditorObjectVersion => "FEditorObjectVersion",
+            Self::FrameworkObjectVersion => "FFrameworkObjectVersion",
+            Self::ReleaseObjectVersion => "FReleaseObjectVersion",
+            Self::BlueprintsObjectVersion => "FBlueprintsObjectVersion",
+            Self::FortniteMainBranchObjectVersion => "FFortniteMainBranchObjectVersion",
+            Self::UE5MainStreamObjectVersion => "FUE5MainStreamObjectVersion",
+            Self::UE5ReleaseStreamObjectVersion => "FUE5ReleaseStreamObjectVersion",
+        }
+    }
+
+    /// Find the known custom version identified by `guid`, if any
+    pub fn from_guid(guid: Guid) -> Option<Self> {
+        Self::ALL.into_iter().find(|known| known.guid() == guid)
+    }
+}
+
+bitflags! {
+    /// Maps to `EPackageFlags` in Engine/Source/Runtime/CoreUObject/Public/UObject/ObjectMacros.h. The `UnusedN` entries
+    /// exist for completeness, presumably those entries were used in the past.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    pub struct PackageFlags: u32 {
+        const None = 0x00000000;
+        const NewlyCreated = 0x00000001;
+        const ClientOptional = 0x00000002;
+        const ServerSideOnly = 0x00000004;
+        const CompiledIn = 0x00000010;
+        const ForDiffing = 0x00000020;
+        const EditorOnly = 0x00000040;
+        const Developer = 0x00000080;
+        const UncookedOnly = 0x00000100;
+        const Cooked = 0x00000200;
+        const ContainsNoAsset = 0x00000400;
+        const Unused1 = 0x00000800;
+        const Unused2 = 0x00001000;
+        const UnversionedProperties = 0x00002000;
+        const ContainsMapData = 0x00004000;
+        const Unused3 = 0x00008000;
+        const Compiling = 0x00010000;
+        const ContainsMap = 0x00020000;
+        const RequiresLocalizationGather = 0x00040000;
+        const Unused4 = 0x00080000;
+        const PlayInEditor = 0x00100000;
+        const ContainsScript = 0x00200000;
+        const DisallowExport = 0x00400000;
+        const Unused5 = 0x00800000;
+        const Unused6 = 0x01000000;
+        const Unused7 = 0x02000000;
+        const Unused8 = 0x04000000;
+        const Unused9 = 0x08000000;
+        const DynamicImports = 0x10000000;
+        const RuntimeGenerated = 0x20000000;
+        const ReloadingForCooker = 0x40000000;
+        const FilterEditorOnly = 0x80000000;
+    }
+}
+
+impl_flags_display!(PackageFlags);
+
+bitflags! {
+    /// Maps to `ECompressionFlags` in Engine/Source/Runtime/Core/Public/Misc/CompressionFlags.h. The low bits are the compression
+    /// method, the rest are hints for the compressor.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    pub struct CompressionFlags: u32 {
+        const ZLIB = 0x00000001;
+        const GZIP = 0x00000002;
+        const Custom = 0x00000004;
+        const BiasMemory = 0x00000010;
+        const BiasSpeed = 0x00000020;
+        const SourceIsPadded = 0x00000080;
+        const ForPackaging = 0x00000100;
+    }
+}
+
+impl_flags_display!(CompressionFlags);
+
+bitflags! {
+    /// Maps to `EObjectFlags` in Engine/Source/Runtime/CoreUObject/Public/UObject/ObjectMacros.h.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    pub struct ObjectFlags: u32 {
+        const NoFlags = 0x00000000;
+        const Public = 0x00000001;
+        const Standalone = 0x00000002;
+        const MarkAsNative = 0x00000004;
+        const Transactional = 0x00000008;
+        const ClassDefaultObject = 0x00000010;
+        const ArchetypeObject = 0x00000020;
+        const Transient = 0x00000040;
+        const MarkAsRootSet = 0x00000080;
+        const TagGarbageTemp = 0x00000100;
+        const NeedInitialization = 0x00000200;
+        const NeedLoad = 0x00000400;
+        const KeepForCooker = 0x00000800;
+        const NeedPostLoad = 0x00001000;
+        const NeedPostLoadSubobjects = 0x00002000;
+        const NewerVersionExists = 0x00004000;
+        const BeginDestroyed = 0x00008000;
+        const FinishDestroyed = 0x00010000;
+        const BeingRegenerated = 0x00020000;
+        const DefaultSubObject = 0x00040000;
+        const WasLoaded = 0x00080000;
+        const TextExportTransient = 0x00100000;
+        const LoadCompleted = 0x00200000;
+        const InheritableComponentTemplate = 0x00400000;
+        const DuplicateTransient = 0x00800000;
+        const StrongRefOnFrame = 0x01000000;
+        const NonPIEDuplicateTransient = 0x02000000;
+        const Dynamic = 0x04000000;
+        const WillBeLoaded = 0x08000000;
+        const HasExternalPackage = 0x10000000;
+        const HasPlaceholderType = 0x20000000;
+        const MirroredGarbage = 0x40000000;
+        const AllocatedInSharedPage = 0x80000000;
+    }
 }
 
-/// Maps to `EObjectFlags` in Engine/Source/Runtime/CoreUObject/Public/UObject/ObjectMacros.h.
-#[derive(Debug)]
-#[repr(u32)]
-pub enum ObjectFlags {
-    NoFlags = 0x00000000,
-    Public = 0x00000001,
-    Standalone = 0x00000002,
-    MarkAsNative = 0x00000004,
-    Transactional = 0x00000008,
-    ClassDefaultObject = 0x00000010,
-    ArchetypeObject = 0x00000020,
-    Transient = 0x00000040,
-    MarkAsRootSet = 0x00000080,
-    TagGarbageTemp = 0x00000100,
-    NeedInitialization = 0x00000200,
-    NeedLoad = 0x00000400,
-    KeepForCooker = 0x00000800,
-    NeedPostLoad = 0x00001000,
-    NeedPostLoadSubobjects = 0x00002000,
-    NewerVersionExists = 0x00004000,
-    BeginDestroyed = 0x00008000,
-    FinishDestroyed = 0x00010000,
-    BeingRegenerated = 0x00020000,
-    DefaultSubObject = 0x00040000,
-    WasLoaded = 0x00080000,
-    TextExportTransient = 0x00100000,
-    LoadCompleted = 0x00200000,
-    InheritableComponentTemplate = 0x00400000,
-    DuplicateTransient = 0x00800000,
-    StrongRefOnFrame = 0x01000000,
-    NonPIEDuplicateTransient = 0x02000000,
-    Dynamic = 0x04000000,
-    WillBeLoaded = 0x08000000,
-    HasExternalPackage = 0x10000000,
-    HasPlaceholderType = 0x20000000,
-    MirroredGarbage = 0x40000000,
-    AllocatedInSharedPage = 0x80000000,
-}
\ No newline at end of file
+impl_flags_display!(ObjectFlags);
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::ObjectReference;
+
 /// Results from parsing a uasset
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -22,6 +24,32 @@ pub enum Error {
     Io(std::io::Error),
     #[error("failed to parse string in asset: {0:?}")]
     InvalidString(std::string::FromUtf8Error),
+    #[error("failed to resolve name: {0}")]
+    InvalidNameIndex(InvalidNameIndexError),
+    #[error("name table has invalid hashes: {0}")]
+    InvalidNameHash(String),
+    #[error("invalid object reference in asset: {0:?}")]
+    InvalidObjectReference(ObjectReference),
+    #[error("outer chain of {0:?} contains a cycle")]
+    OuterCycle(ObjectReference),
+    #[error("failed to read property data: {0}")]
+    InvalidPropertyData(String),
+    #[error("no property schema for {0}")]
+    MissingSchema(String),
+    #[error("invalid property schema: {0}")]
+    InvalidSchema(String),
+    #[error("failed to read bulk data: {0}")]
+    InvalidBulkData(String),
+    #[error("failed to read payload table of contents: {0}")]
+    InvalidPayloadToc(String),
+    #[error("failed to read gatherable text data: {0}")]
+    InvalidGatherableTextData(String),
+    #[error("failed to read preload dependencies: {0}")]
+    InvalidPreloadDependencies(String),
+    #[error("package has compressed chunks, it can only be read with `AssetHeader::new_compressed`")]
+    CompressedPackage,
+    #[error("failed to decompress package: {0}")]
+    InvalidCompressedPackage(String),
 }
 
 impl From<binread::Error> for Error {
@@ -36,6 +64,12 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<InvalidNameIndexError> for Error {
+    fn from(error: InvalidNameIndexError) -> Self {
+        Error::InvalidNameIndex(error)
+    }
+}
+
 /// Error when attempting to resolve an index
 #[derive(Error, Debug)]
 #[error("invalid name index in asset: {0:?}")]
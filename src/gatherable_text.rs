@@ -0,0 +1,141 @@
+use crate::{
+    Error, Result,
+    archive::Archive,
+    serialization::{Parseable, UnrealString},
+};
+use binread::BinReaderExt;
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek},
+};
+
+/// How deeply metadata objects and arrays can be nested before we consider the data corrupt
+const MAX_METADATA_DEPTH: usize = 64;
+
+/// Metadata attached to a localizable text, mapping keys to values (C++ name: `FLocMetadataObject`)
+pub type LocMetadataObject = BTreeMap<String, LocMetadataValue>;
+
+/// A value in a [`LocMetadataObject`] (C++ name: `FLocMetadataValue`)
+#[derive(Clone, Debug, PartialEq)]
+pub enum LocMetadataValue {
+    Null,
+    String(String),
+    Boolean(bool),
+    Array(Vec<LocMetadataValue>),
+    Object(LocMetadataObject),
+}
+
+/// A place a localizable text is used, i.e. a property of an object in the package (C++ name: `FTextSourceSiteContext`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSourceSiteContext {
+    /// The key of the text, which together with the namespace identifies it (C++ name: `KeyName`)
+    pub key: String,
+    /// Path of the property that holds the text, e.g. `/Game/Path/Package.Object.Property` (C++ name: `SiteDescription`)
+    pub site_description: String,
+    /// True if the text is only used in the editor, e.g. a tooltip or a category (C++ name: `IsEditorOnly`)
+    pub is_editor_only: bool,
+    /// True if the text doesn't need to be translated (C++ name: `IsOptional`)
+    pub is_optional: bool,
+    /// (C++ name: `InfoMetaData`)
+    pub info_metadata: LocMetadataObject,
+    /// (C++ name: `KeyMetaData`)
+    pub key_metadata: LocMetadataObject,
+}
+
+/// A localizable text in the package along with everywhere it's used, as gathered when the package was saved. Read with
+/// [`crate::AssetHeader::gatherable_text_data`]. (C++ name: `FGatherableTextData`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct GatherableTextData {
+    /// (C++ name: `NamespaceName`)
+    pub namespace: String,
+    /// The text in the source language (C++ name: `SourceData.SourceString`)
+    pub source_string: String,
+    /// (C++ name: `SourceData.SourceStringMetaData`)
+    pub source_string_metadata: LocMetadataObject,
+    /// (C++ name: `SourceSiteContexts`)
+    pub source_site_contexts: Vec<TextSourceSiteContext>,
+}
+
+impl GatherableTextData {
+    /// Read an entry at the current position of `archive` (see `operator<<(FArchive&, FGatherableTextData&)`)
+    pub(crate) fn parse<R: Read + Seek>(archive: &mut Archive<R>) -> Result<Self> {
+        let namespace = UnrealString::parse_inline(archive)?;
+        let source_string = UnrealString::parse_inline(archive)?;
+        let source_string_metadata = read_metadata_object(archive, 0)?;
+
+        let count: i32 = archive.read_le()?;
+        let mut source_site_contexts = Vec::with_capacity(count.clamp(0, 1024) as usize);
+        for _ in 0..count {
+            source_site_contexts.push(TextSourceSiteContext {
+                key: UnrealString::parse_inline(archive)?,
+                site_description: UnrealString::parse_inline(archive)?,
+                is_editor_only: read_bool(archive)?,
+                is_optional: read_bool(archive)?,
+                info_metadata: read_metadata_object(archive, 0)?,
+                key_metadata: read_metadata_object(archive, 0)?,
+            });
+        }
+
+        Ok(Self {
+            namespace,
+            source_string,
+            source_string_metadata,
+            source_site_contexts,
+        })
+    }
+}
+
+/// Read a `bool` the way `FArchive` serializes it, as a 32-bit integer
+fn read_bool<R: Read + Seek>(archive: &mut Archive<R>) -> Result<bool> {
+    Ok(archive.read_le::<u32>()? != 0)
+}
+
+/// Read a `FLocMetadataObject`, which is a count followed by keys & values
+fn read_metadata_object<R: Read + Seek>(
+    archive: &mut Archive<R>,
+    depth: usize,
+) -> Result<LocMetadataObject> {
+    let count: i32 = archive.read_le()?;
+    let mut object = LocMetadataObject::new();
+    for _ in 0..count {
+        let key = UnrealString::parse_inline(archive)?;
+        let value = read_metadata_value(archive, depth)?;
+        object.insert(key, value);
+    }
+    Ok(object)
+}
+
+/// Read a `FLocMetadataValue`, which is an `ELocMetadataType` followed by the value
+fn read_metadata_value<R: Read + Seek>(
+    archive: &mut Archive<R>,
+    depth: usize,
+) -> Result<LocMetadataValue> {
+    if depth >= MAX_METADATA_DEPTH {
+        return Err(Error::InvalidGatherableTextData(
+            "localization metadata is nested too deeply".to_string(),
+        ));
+    }
+
+    // The types are `None`, `Null`, `String`, `Boolean`, `Array` and `Object`, in that order
+    let metadata_type: i32 = archive.read_le()?;
+    Ok(match metadata_type {
+        0 | 1 => LocMetadataValue::Null,
+        2 => LocMetadataValue::String(UnrealString::parse_inline(archive)?),
+        3 => LocMetadataValue::Boolean(read_bool(archive)?),
+        4 => {
+            let count: i32 = archive.read_le()?;
+            let mut values = Vec::with_capacity(count.clamp(0, 1024) as usize);
+            for _ in 0..count {
+                values.push(read_metadata_value(archive, depth + 1)?);
+            }
+            LocMetadataValue::Array(values)
+        }
+        5 => LocMetadataValue::Object(read_metadata_object(archive, depth + 1)?),
+        _ => {
+            return Err(Error::InvalidGatherableTextData(format!(
+                "unknown localization metadata type {}",
+                metadata_type
+            )));
+        }
+    })
+}
@@ -47,31 +47,73 @@
 //!
 //! * `commandline-tool` -
 //!   Allows the building of a `uasset` command line tool that can be used to inspect specific assets.
+//! * `json-schemas` -
+//!   Allows loading [`PropertySchemas`] from JSON files, for reading properties of cooked assets.
 
 mod archive;
+mod asset_index;
+mod bulk_data;
+mod compressed_package;
 pub mod enums;
 mod error;
+mod gatherable_text;
+mod names;
+mod package;
+mod payload_toc;
+mod properties;
 mod serialization;
+mod unversioned;
+mod world_tile_info;
 
 use archive::SerializedObjectVersion;
-use binread::BinReaderExt;
+use binread::{BinRead, BinReaderExt};
 use serialization::{
-    ArrayStreamInfo, Parseable, Skippable, StreamInfo, UnrealArray, UnrealArrayIterator,
-    UnrealClassImport, UnrealCompressedChunk, UnrealCustomVersion, UnrealEngineVersion,
-    UnrealGenerationInfo, UnrealGuid, UnrealGuidCustomVersion, UnrealNameEntryWithHash,
-    UnrealString, UnrealThumbnailInfo,
+    ArrayStreamInfo, Parseable, StreamInfo, UnrealArray, UnrealArrayIterator,
+    UnrealAssetRegistryObject, UnrealBitArray, UnrealClassImport, UnrealCompressedChunk,
+    UnrealCustomVersion, UnrealEngineVersion, UnrealGenerationInfo, UnrealGuidCustomVersion,
+    UnrealNameEntryWithHash, UnrealNameReference, UnrealPackageBuildDependency,
+    UnrealObjectThumbnail, UnrealPackageIndex, UnrealSoftObjectPath, UnrealString,
+    UnrealThumbnailInfo,
 };
 use std::{
     borrow::Cow,
     cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    fmt,
     io::{Read, Seek, SeekFrom},
     num::NonZeroU32,
+    sync::OnceLock,
 };
 
-pub use archive::{Archive, CustomVersionSerializationFormat};
-pub use enums::{ObjectVersion, ObjectVersionUE5, PackageFlags};
+pub use archive::{Archive, CustomVersionSerializationFormat, SerializedCustomVersion};
+pub use asset_index::{
+    is_uasset, AssetIndex, IndexedPackage, MountPoints, PackageReference, ReferenceKind,
+    UASSET_EXTENSIONS,
+};
+pub use bulk_data::{
+    BulkDataFile, BulkDataFlags, BulkDataHeader, BulkDataLocation, BulkDataReader, DuplicateBulkData,
+    ObjectDataResource, ObjectDataResourceFlags, ZlibChunkReader,
+};
+pub use compressed_package::CompressedPackageReader;
+pub use enums::{
+    CompressionFlags, KnownCustomVersion, ObjectFlags, ObjectVersion, ObjectVersionUE5, PackageFlags,
+};
 pub use error::{Error, InvalidNameIndexError, Result};
-use crate::serialization::UnrealObjectExport;
+pub use gatherable_text::{
+    GatherableTextData, LocMetadataObject, LocMetadataValue, TextSourceSiteContext,
+};
+pub use names::{NameHashes, NameLookup};
+pub use package::{AssetPackage, PackageReader};
+pub use payload_toc::{IoHash, PayloadAccessMode, PayloadToc, PayloadTocEntry};
+pub use properties::{
+    OverriddenPropertyOperation, Property, PropertyTag, PropertyTypeName, PropertyValue, Text,
+};
+use properties::PropertyReader;
+pub use unversioned::{PropertySchema, PropertySchemaProvider, PropertySchemas, StructSchema};
+pub use world_tile_info::{
+    BoundingBox, IntVector, Vector, WorldTileInfo, WorldTileLayer, WorldTileLodInfo,
+};
+use crate::serialization::{SerializedAssetPath, SerializedSoftObjectPath, UnrealObjectExport};
 
 /// A reference to a name in the [`AssetHeader::names`] name table. You can use [`AssetHeader::resolve_name`] to get a human-readable
 /// string from a `NameReference`. It only makes sense to compare `NameReference`s from the same `AssetHeader`.
@@ -84,8 +126,72 @@ pub struct NameReference {
     pub number: Option<NonZeroU32>,
 }
 
+/// A globally unique identifier, serialized as four 32-bit integers. (C++ name: `FGuid`)
+#[derive(BinRead, Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Guid {
+    pub a: u32,
+    pub b: u32,
+    pub c: u32,
+    pub d: u32,
+}
+
+impl Guid {
+    pub const fn new(a: u32, b: u32, c: u32, d: u32) -> Self {
+        Self { a, b, c, d }
+    }
+
+    /// True if all the components of the GUID are zero, which the engine treats as an invalid GUID
+    pub fn is_zero(&self) -> bool {
+        self.a == 0 && self.b == 0 && self.c == 0 && self.d == 0
+    }
+}
+
+impl fmt::Display for Guid {
+    /// Formats the GUID the same way `FGuid::ToString` does by default (`EGuidFormats::Digits`)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08X}{:08X}{:08X}{:08X}", self.a, self.b, self.c, self.d)
+    }
+}
+
+/// The version of a specific engine subsystem or plugin that an asset was saved with, used to gate serialization
+/// changes that are not covered by [`ObjectVersion`] and [`ObjectVersionUE5`]. (C++ name: `FCustomVersion`)
+#[derive(Clone, Debug)]
+pub struct CustomVersion {
+    /// Unique identifier for this custom version (C++ name: `Key`)
+    pub guid: Guid,
+    /// The version number for the custom version (C++ name: `Version`)
+    pub version: i32,
+    /// Name of the custom version. This is only serialized with [`CustomVersionSerializationFormat::Guids`], for newer assets
+    /// we fill it in if the `guid` refers to a [`KnownCustomVersion`]. (C++ name: `FriendlyName`)
+    pub friendly_name: Option<String>,
+}
+
+/// The number of exports and names a package had when it was saved. The engine adds an entry every time a package is saved,
+/// but it's only kept around for the current save nowadays. (C++ name: `FGenerationInfo`)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GenerationInfo {
+    /// (C++ name: `ExportCount`)
+    pub export_count: i32,
+    /// (C++ name: `NameCount`)
+    pub name_count: i32,
+}
+
+/// A range of the uncompressed package and where it's stored compressed, for packages saved with compressed chunks by older versions
+/// of the engine. (C++ name: `FCompressedChunk`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CompressedChunk {
+    /// (C++ name: `UncompressedOffset`)
+    pub uncompressed_offset: i32,
+    /// (C++ name: `UncompressedSize`)
+    pub uncompressed_size: i32,
+    /// Where the data written by `FArchive::SerializeCompressed` for this chunk starts in the file (C++ name: `CompressedOffset`)
+    pub compressed_offset: i32,
+    /// (C++ name: `CompressedSize`)
+    pub compressed_size: i32,
+}
+
 /// A reference to either an import or an export in the asset.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum ObjectReference {
     None,
     Export { export_index: usize },
@@ -124,7 +230,7 @@ pub struct ObjectExport {
     template_index: i32,
 
     /// Object flags for this export
-    pub object_flags: u32, // TODO: Use ObjectFlags enum
+    pub object_flags: ObjectFlags,
 
     /// Number of bytes serialized by this export
     pub serial_size: i64,
@@ -150,9 +256,10 @@ pub struct ObjectExport {
     pub generate_public_hash: bool,
 
     /// If this is a top level package, the original package file flags
-    pub package_flags: u32, // TODO: Use PackageFlags enum
+    pub package_flags: PackageFlags,
 
-    // Contiguous blocks with offsets relative to each other, -1 means "not present"
+    // Contiguous blocks with offsets relative to each other, -1 means "not present". Resolved by
+    // [`AssetHeader::export_preload_dependencies`].
     pub first_export_dependency: i32,
     pub serialization_before_serialization_dependencies: i32,
     pub create_before_serialization_dependencies: i32,
@@ -181,6 +288,20 @@ impl ObjectExport {
     }
 }
 
+/// The objects that have to be created or serialized before an export can be, which the engine uses to order the loading of
+/// cooked packages. Read with [`AssetHeader::export_preload_dependencies`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PreloadDependencies {
+    /// Objects that must be serialized before this export is serialized (C++ name: `SerializationBeforeSerializationDependencies`)
+    pub serialization_before_serialization: Vec<ObjectReference>,
+    /// Objects that must be created before this export is serialized (C++ name: `CreateBeforeSerializationDependencies`)
+    pub create_before_serialization: Vec<ObjectReference>,
+    /// Objects that must be serialized before this export is created (C++ name: `SerializationBeforeCreateDependencies`)
+    pub serialization_before_create: Vec<ObjectReference>,
+    /// Objects that must be created before this export is created (C++ name: `CreateBeforeCreateDependencies`)
+    pub create_before_create: Vec<ObjectReference>,
+}
+
 /// A reference to an object in another package. Typically accessed through [`AssetHeader::package_import_iter`], but you can also
 /// manually resolve the [`NameReference`]s. (C++ name: `FObjectImport`)
 #[derive(Debug)]
@@ -206,6 +327,42 @@ impl ObjectImport {
     }
 }
 
+/// A reference to an object by its path, where the object might live in a package that isn't loaded. (C++ name: `FSoftObjectPath`)
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SoftObjectPath {
+    /// Name of the package containing the top level asset, e.g. `/Game/Maps/MyMap` (C++ name: `AssetPath.PackageName`)
+    pub package_name: String,
+    /// Name of the top level asset in the package, e.g. `MyMap` (C++ name: `AssetPath.AssetName`)
+    pub asset_name: String,
+    /// Optional path to a subobject of the top level asset, e.g. `PersistentLevel.MyActor` (C++ name: `SubPathString`)
+    pub sub_path: String,
+}
+
+impl SoftObjectPath {
+    /// True if this path doesn't reference anything
+    pub fn is_null(&self) -> bool {
+        self.package_name.is_empty() && self.asset_name.is_empty() && self.sub_path.is_empty()
+    }
+}
+
+impl fmt::Display for SoftObjectPath {
+    /// Formats the path the same way `FSoftObjectPath::ToString` does, i.e. `/Game/Package.Asset:SubPath`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.package_name.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, "{}", self.package_name)?;
+        if !self.asset_name.is_empty() {
+            write!(f, ".{}", self.asset_name)?;
+        }
+        if !self.sub_path.is_empty() {
+            write!(f, ":{}", self.sub_path)?;
+        }
+        Ok(())
+    }
+}
+
 /// Iterator over the imported packages in a given [`AssetHeader`]
 pub struct ImportIterator<'a, R> {
     package: &'a AssetHeader<R>,
@@ -274,6 +431,140 @@ pub struct ThumbnailInfo {
     pub file_offset: i32,
 }
 
+/// How the image data of an [`ObjectThumbnail`] is compressed
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ThumbnailFormat {
+    Png,
+    Jpeg,
+}
+
+impl ThumbnailFormat {
+    /// Extension for an image file in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::Jpeg => "jpg",
+        }
+    }
+}
+
+/// A thumbnail image of an object in the package, stored at [`ThumbnailInfo::file_offset`] and read with
+/// [`AssetHeader::read_thumbnail`] (C++ name: `FObjectThumbnail`)
+#[derive(Debug)]
+pub struct ObjectThumbnail {
+    /// (C++ name: `ImageWidth`)
+    pub width: i32,
+    /// (C++ name: `ImageHeight`)
+    pub height: i32,
+    /// The format of `compressed_image_data`, which is stored as the sign of the height
+    pub format: ThumbnailFormat,
+    /// The complete image file (C++ name: `CompressedImageData`)
+    pub compressed_image_data: Vec<u8>,
+}
+
+impl ObjectThumbnail {
+    /// True if there's no image, which is the case for assets that don't render a thumbnail (like blueprints without a mesh)
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0 || self.compressed_image_data.is_empty()
+    }
+}
+
+/// The asset registry information for a single object in the package, as gathered by the editor when the package was
+/// saved. (See `UE::AssetRegistry::WritePackageData`)
+#[derive(Debug)]
+pub struct AssetRegistryObject {
+    /// Path of the object, relative to the package, e.g. `MyBlueprint` (C++ name: `ObjectPath`)
+    pub object_path: String,
+    /// Full path of the class of the object, e.g. `/Script/Engine.Blueprint` (C++ name: `ObjectClassName`)
+    pub object_class_name: String,
+    /// Asset registry tags and their values, e.g. `ParentClass` or `NativeParentClass` for a blueprint (C++ name: `TagsAndValues`)
+    pub tags: BTreeMap<String, String>,
+}
+
+/// A package that is needed to build (cook) a package, but that isn't referenced by it (C++ name: `ExtraPackageDependencies`)
+#[derive(Debug)]
+pub struct PackageBuildDependency {
+    pub package_name: String,
+    /// The `UE::AssetRegistry::EExtraDependencyFlags` for this dependency
+    pub flags: u32,
+}
+
+/// Dependency information gathered by the asset registry, used to tell editor-only references apart from game references.
+/// (See `UE::AssetRegistry::ReadPackageDataDependencies`)
+#[derive(Debug)]
+pub struct AssetRegistryDependencyData {
+    /// For each entry in [`AssetHeader::imports`], true if it's used in game and not just in the editor (C++ name: `ImportUsedInGame`)
+    pub import_used_in_game: Vec<bool>,
+    /// For each entry in [`AssetHeader::soft_package_references`], true if it's used in game and not just in the editor
+    /// (C++ name: `SoftPackageUsedInGame`)
+    pub soft_package_used_in_game: Vec<bool>,
+    /// Only serialized with [`ObjectVersionUE5::ASSETREGISTRY_PACKAGEBUILDDEPENDENCIES`] or later
+    pub package_build_dependencies: Vec<PackageBuildDependency>,
+}
+
+/// The asset registry data stored behind [`AssetHeader::asset_registry_data_offset`], which lets tools discover assets without
+/// loading them.
+#[derive(Debug, Default)]
+pub struct AssetRegistryData {
+    /// The assets in this package, typically just one
+    pub objects: Vec<AssetRegistryObject>,
+    /// Only present in uncooked packages saved with [`ObjectVersion::VER_UE4_ASSETREGISTRY_DEPENDENCYFLAGS`] or later
+    pub dependency_data: Option<AssetRegistryDependencyData>,
+}
+
+/// Look up the string representation for a given [`NameReference`] in a name table
+fn resolve_name<'a>(
+    names: &'a [String],
+    name_reference: &NameReference,
+) -> std::result::Result<Cow<'a, str>, InvalidNameIndexError> {
+    let index = name_reference.index as usize;
+    if names.len() > index {
+        let mut name = Cow::from(&names[index]);
+        if let Some(number) = name_reference.number {
+            name.to_mut().push_str(&format!("_{}", number.get() - 1));
+        }
+        Ok(name)
+    } else {
+        Err(InvalidNameIndexError(name_reference.index))
+    }
+}
+
+/// Resolve the names of a serialized `FSoftObjectPath` using a name table
+fn resolve_soft_object_path(
+    names: &[String],
+    serialized_path: &SerializedSoftObjectPath,
+) -> std::result::Result<SoftObjectPath, InvalidNameIndexError> {
+    // `NAME_None` is used for empty paths, which is serialized as a reference to the "None" entry in the name table
+    let resolve = |name: &NameReference| -> std::result::Result<String, InvalidNameIndexError> {
+        let name = resolve_name(names, name)?;
+        Ok(if name == "None" {
+            String::new()
+        } else {
+            name.into_owned()
+        })
+    };
+
+    let (package_name, asset_name) = match &serialized_path.asset_path {
+        SerializedAssetPath::TopLevel {
+            package_name,
+            asset_name,
+        } => (resolve(package_name)?, resolve(asset_name)?),
+        SerializedAssetPath::Combined(asset_path_name) => {
+            let asset_path_name = resolve(asset_path_name)?;
+            match asset_path_name.split_once('.') {
+                Some((package_name, asset_name)) => (package_name.to_string(), asset_name.to_string()),
+                None => (asset_path_name, String::new()),
+            }
+        }
+    };
+
+    Ok(SoftObjectPath {
+        package_name,
+        asset_name,
+        sub_path: serialized_path.sub_path.clone(),
+    })
+}
+
 /// A table of contents for a uasset loaded from disk, containing all the shared package summary information.
 /// This roughly maps to `FPackageFileSummary` in Engine/Source/Runtime/CoreUObject/Public/UObject/PackageFileSummary.h, except we
 /// load some of the indirectly referenced data (i.e. names, imports, exports).
@@ -285,16 +576,19 @@ pub struct AssetHeader<R> {
     /// The last name this package was saved with (C++ name: `PackageName`)
     pub package_name: String,
     /// Package flags like whether this was serialized for the editor (C++ name: `PackagesFlags`)
-    pub package_flags: u32, // TODO: Use PackageFlags enum
+    pub package_flags: PackageFlags,
     /// Table of names used by this asset (C++ name: `NameCount` and `NameOffset`)
     pub names: Vec<String>,
+    /// The hashes stored with each name in [`AssetHeader::names`], or empty if the asset is older than
+    /// [`ObjectVersion::VER_UE4_NAME_HASHES_SERIALIZED`]. See [`AssetHeader::verify_name_hashes`].
+    pub name_hashes: Vec<NameHashes>,
     /// Number of soft object paths references contained in this package (C++ name: `SoftObjectPathsCount`)
     pub soft_object_paths_count: i32,
     /// Location into the file on disk for the soft object paths reference list (C++ name: `SoftObjectPathsOffset`)
     pub soft_object_paths_offset: i32,
     /// Localization ID for this package (C++ name: `LocalizationId`)
     pub localization_id: Option<String>,
-    /// Number of gatherable text data entries (C++ name: `GatherableTextDataCount`)
+    /// Number of gatherable text data entries, see [`AssetHeader::gatherable_text_data`] (C++ name: `GatherableTextDataCount`)
     pub gatherable_text_data_count: i32,
     /// Location on disk of gatherable text data entries (C++ name: `GatherableTextDataOffset`)
     pub gatherable_text_data_offset: i32,
@@ -308,16 +602,29 @@ pub struct AssetHeader<R> {
     pub soft_package_references_count: i32,
     /// Location on disk of the soft package references (C++ name: `SoftPackageReferencesOffset`)
     pub soft_package_references_offset: i32,
-    /// Location of `SearchableNamesMap` data (C++ name: `SearchableNamesOffset`)
+    /// Location of `SearchableNamesMap` data, see [`AssetHeader::searchable_names`] (C++ name: `SearchableNamesOffset`)
     pub searchable_names_offset: Option<i32>,
     /// Offset of the thumbnail table (C++ name: `ThumbnailTableOffset`)
     pub thumbnail_table_offset: i32,
+    /// Identifier of this package that changes every time it's saved (C++ name: `Guid`)
+    pub guid: Guid,
+    /// Identifier of this package that stays the same when it's saved or renamed. This is only serialized in packages with
+    /// editor-only data, and older packages use their `guid` instead. (C++ name: `PersistentGuid`)
+    pub persistent_guid: Option<Guid>,
+    /// Persistent GUID of the package that owned this one, which was removed with `VER_UE4_NON_OUTER_PACKAGE_IMPORT`
+    /// (C++ name: `OwnerPersistentGuid`)
+    pub owner_persistent_guid: Option<Guid>,
+    /// Export and name counts of this package when it was saved (C++ name: `Generations`)
+    pub generations: Vec<GenerationInfo>,
     /// Information about the engine version the asset was saved with (C++ name: `SavedByEngineVersion`)
     pub engine_version: UnrealEngineVersion,
     /// Information about the engine version the asset is compatible with (for hotfix support) (C++ name: `CompatibleWithEngineVersion`)
     pub compatible_with_engine_version: UnrealEngineVersion,
     /// Flags dictating compression settings for this asset (C++ name: `CompressionFlags`)
-    pub compression_flags: u32,
+    pub compression_flags: CompressionFlags,
+    /// Chunks of the package that are compressed, which is only the case for packages cooked by older versions of the engine.
+    /// These packages can be read with [`AssetHeader::new_compressed`]. (C++ name: `CompressedChunks`)
+    pub compressed_chunks: Vec<CompressedChunk>,
     /// This is a random number in assets created by the shipping build of the editor, and a crc32 of the uppercased filename
     /// otherwise. Weird. Used to determine if an asset was made "by a modder or by Epic (or licensee)". (C++ name: `PackageSource`)
     pub package_source: u32,
@@ -329,7 +636,8 @@ pub struct AssetHeader<R> {
     pub asset_registry_data_offset: i32,
     /// Offset to the location in the file where the bulkdata starts  (C++ name: `BulkDataStartOffset`)
     pub bulk_data_start_offset: i64,
-    /// Offset to the location in the file where the `FWorldTileInfo` data starts (C++ name: `WorldTileInfoDataOffset`)
+    /// Offset to the location in the file where the `FWorldTileInfo` data starts, see [`AssetHeader::world_tile_info`]
+    /// (C++ name: `WorldTileInfoDataOffset`)
     pub world_tile_info_data_offset: Option<i32>,
     /// Streaming install chunk ids (C++ name: `ChunkIDs`)
     pub chunk_ids: Vec<i32>,
@@ -339,52 +647,58 @@ pub struct AssetHeader<R> {
     pub preload_dependency_offset: i32,
     /// Number of names that are referenced from serialized export data (sorted first in the name map) (C++ name: `NamesReferencedFromExportDataCount`)
     pub names_referenced_from_export_data_count: i32,
-    /// Location into the file on disk for the payload table of contents data (C++ name: `PayloadTocOffset`)
+    /// Location into the file on disk for the payload table of contents data, see [`AssetHeader::payload_toc`]
+    /// (C++ name: `PayloadTocOffset`)
     pub payload_toc_offset: i64,
-    /// Location into the file of the data resource(s) (C++ name: `DataResourceOffset `)
+    /// Location into the file of the data resource table, see [`AssetHeader::data_resources`] (C++ name: `DataResourceOffset`)
     pub data_resource_offset: Option<i32>,
+    /// Index of [`AssetHeader::names`] used by [`AssetHeader::find_name`], built the first time a name is looked up
+    name_lookup: OnceLock<NameLookup>,
+    /// The soft object paths from [`AssetHeader::soft_object_paths`] that properties refer to, read by the first call to
+    /// [`AssetHeader::export_properties`]
+    soft_object_paths: Option<Vec<SoftObjectPath>>,
 }
 
 impl<R> AssetHeader<R>
 where
     R: Seek + Read,
 {
-    /// Parse an [`AssetHeader`] from the given reader, assuming a little endian uasset
+    /// Parse an [`AssetHeader`] from the given reader, assuming a little endian uasset. Packages with compressed chunks are
+    /// rejected, see [`AssetHeader::new_compressed`].
     pub fn new(reader: R) -> Result<Self> {
+        let (mut header, tables) = Self::parse_summary(reader)?;
+        if !header.compressed_chunks.is_empty() {
+            return Err(Error::CompressedPackage);
+        }
+        header.read_tables(&tables)?;
+        Ok(header)
+    }
+
+    /// Parse the package summary at the start of the package, without reading the tables it points to
+    fn parse_summary(reader: R) -> Result<(Self, PackageTables)> {
         let mut archive = Archive::new(reader)?;
 
-        // Parse and seek past `CustomVersionContainer`
-        let custom_versions_stream_info = ArrayStreamInfo::from_current_position(&mut archive)?;
-        match archive.custom_version_serialization_format() {
+        // Parse `CustomVersionContainer`, and store it in the archive so that later parsing can gate on it
+        archive.custom_versions = match archive.custom_version_serialization_format() {
             CustomVersionSerializationFormat::Guids => {
-                let _custom_versions = UnrealArray::<UnrealGuidCustomVersion>::seek_past_with_info(
-                    &mut archive,
-                    &custom_versions_stream_info,
-                )?;
+                UnrealArray::<UnrealGuidCustomVersion>::parse_inline(&mut archive)?
             }
             CustomVersionSerializationFormat::Optimized => {
-                let _custom_versions = UnrealArray::<UnrealCustomVersion>::seek_past_with_info(
-                    &mut archive,
-                    &custom_versions_stream_info,
-                )?;
+                UnrealArray::<UnrealCustomVersion>::parse_inline(&mut archive)?
             }
-        }
+        };
 
         let total_header_size = archive.read_le()?;
 
         let package_name = UnrealString::parse_inline(&mut archive)?;
 
-        let package_flags = archive.read_le()?;
-        let has_editor_only_data = (package_flags & PackageFlags::FilterEditorOnly as u32) == 0;
+        let package_flags = PackageFlags::from_bits_retain(archive.read_le()?);
+        let has_editor_only_data = !package_flags.contains(PackageFlags::FilterEditorOnly);
         archive.with_editoronly_data = has_editor_only_data;
 
-        let names = if archive.serialized_with(ObjectVersion::VER_UE4_NAME_HASHES_SERIALIZED) {
-            UnrealArray::<UnrealNameEntryWithHash>::parse_indirect(&mut archive)?
-        } else {
-            UnrealArray::<UnrealString>::parse_indirect(&mut archive)?
-        };
+        let names = ArrayStreamInfo::from_indirect_reference(&mut archive)?;
 
-        // This is an indirect array of `FSoftObjectPath` entries, which we could parse.
+        // This is an indirect array of `FSoftObjectPath` entries, which is parsed on demand by `soft_object_paths`.
         let has_soft_object_paths =
             archive.serialized_with(ObjectVersionUE5::ADD_SOFTOBJECTPATH_LIST);
         let (soft_object_paths_count, soft_object_paths_offset) = if has_soft_object_paths {
@@ -410,9 +724,9 @@ where
             (0, 0)
         };
 
-        let exports = UnrealArray::<UnrealObjectExport>::parse_indirect(&mut archive)?;
+        let exports = ArrayStreamInfo::from_indirect_reference(&mut archive)?;
 
-        let imports = UnrealArray::<UnrealClassImport>::parse_indirect(&mut archive)?;
+        let imports = ArrayStreamInfo::from_indirect_reference(&mut archive)?;
 
         let depends_offset = archive.read_le()?;
 
@@ -435,27 +749,25 @@ where
 
         let thumbnail_table_offset = archive.read_le()?;
 
-        let _guid = UnrealGuid::seek_past(&mut archive)?;
+        let guid = archive.read_le()?;
         let supports_package_owner =
             archive.serialized_with(ObjectVersion::VER_UE4_ADDED_PACKAGE_OWNER);
-        if supports_package_owner && has_editor_only_data {
-            let _persistent_guid = UnrealGuid::seek_past(&mut archive)?;
-            let before_non_outer_package_import =
-                archive.serialized_without(ObjectVersion::VER_UE4_NON_OUTER_PACKAGE_IMPORT);
-            if before_non_outer_package_import {
-                let _owner_persistent_guid = UnrealGuid::seek_past(&mut archive)?;
-            }
-        }
+        let (persistent_guid, owner_persistent_guid) =
+            if supports_package_owner && has_editor_only_data {
+                let persistent_guid = archive.read_le()?;
+                let before_non_outer_package_import =
+                    archive.serialized_without(ObjectVersion::VER_UE4_NON_OUTER_PACKAGE_IMPORT);
+                let owner_persistent_guid = if before_non_outer_package_import {
+                    Some(archive.read_le()?)
+                } else {
+                    None
+                };
+                (Some(persistent_guid), owner_persistent_guid)
+            } else {
+                (None, None)
+            };
 
-        let num_generations: i32 = archive.read_le()?;
-        let generations_stream_info = ArrayStreamInfo {
-            offset: archive.stream_position()?,
-            count: num_generations as u64,
-        };
-        let _generations = UnrealArray::<UnrealGenerationInfo>::seek_past_with_info(
-            &mut archive,
-            &generations_stream_info,
-        )?;
+        let generations = UnrealArray::<UnrealGenerationInfo>::parse_inline(&mut archive)?;
 
         let has_engine_version_object =
             archive.serialized_with(ObjectVersion::VER_UE4_ENGINE_VERSION_OBJECT);
@@ -477,18 +789,10 @@ where
             engine_version.clone()
         };
 
-        let compression_flags = archive.read_le()?;
+        let compression_flags = CompressionFlags::from_bits_retain(archive.read_le()?);
 
-        // The engine will refuse to load any package with compressed chunks, but it doesn't hurt for us to just skip past them.
-        let num_compressed_chunks: i32 = archive.read_le()?;
-        let compressed_chunk_stream_info = ArrayStreamInfo {
-            offset: archive.stream_position()?,
-            count: num_compressed_chunks as u64,
-        };
-        let _compressed_chunks = UnrealArray::<UnrealCompressedChunk>::seek_past_with_info(
-            &mut archive,
-            &compressed_chunk_stream_info,
-        )?;
+        // Newer versions of the engine refuse to load any package with compressed chunks
+        let compressed_chunks = UnrealArray::<UnrealCompressedChunk>::parse_inline(&mut archive)?;
 
         let package_source = archive.read_le()?;
 
@@ -545,10 +849,11 @@ where
 
         let has_names_referenced_from_export_data =
             archive.serialized_with(ObjectVersionUE5::NAMES_REFERENCED_FROM_EXPORT_DATA);
+        // Before this was serialized any name could be referenced from export data, `read_tables` fills in how many there are
         let names_referenced_from_export_data_count = if has_names_referenced_from_export_data {
             archive.read_le()?
         } else {
-            names.len() as i32
+            0
         };
 
         let has_payload_toc = archive.serialized_with(ObjectVersionUE5::PAYLOAD_TOC);
@@ -570,12 +875,94 @@ where
             None
         };
 
-        Ok(Self {
+        let header = Self {
             archive,
             total_header_size,
             package_name,
             package_flags,
+            names: Vec::new(),
+            name_hashes: Vec::new(),
+            soft_object_paths_count,
+            soft_object_paths_offset,
+            localization_id,
+            gatherable_text_data_count,
+            gatherable_text_data_offset,
+            exports: Vec::new(),
+            imports: Vec::new(),
+            depends_offset,
+            soft_package_references_count,
+            soft_package_references_offset,
+            searchable_names_offset,
+            thumbnail_table_offset,
+            guid,
+            persistent_guid,
+            owner_persistent_guid,
+            generations,
+            engine_version,
+            compatible_with_engine_version,
+            compression_flags,
+            compressed_chunks,
+            package_source,
+            additional_packages_to_cook,
+            texture_allocations,
+            asset_registry_data_offset,
+            bulk_data_start_offset,
+            world_tile_info_data_offset,
+            chunk_ids,
+            preload_dependency_count,
+            preload_dependency_offset,
+            names_referenced_from_export_data_count,
+            payload_toc_offset,
+            data_resource_offset,
+            name_lookup: OnceLock::new(),
+            soft_object_paths: None,
+        };
+        let tables = PackageTables {
             names,
+            exports,
+            imports,
+        };
+        Ok((header, tables))
+    }
+
+    /// Read the name, export and import tables, which are stored after the package summary
+    fn read_tables(&mut self, tables: &PackageTables) -> Result<()> {
+        if self
+            .archive
+            .serialized_with(ObjectVersion::VER_UE4_NAME_HASHES_SERIALIZED)
+        {
+            (self.names, self.name_hashes) =
+                UnrealArray::<UnrealNameEntryWithHash>::parse_with_info(&mut self.archive, &tables.names)?
+                    .into_iter()
+                    .unzip();
+        } else {
+            self.names = UnrealArray::<UnrealString>::parse_with_info(&mut self.archive, &tables.names)?;
+        }
+        self.exports =
+            UnrealArray::<UnrealObjectExport>::parse_with_info(&mut self.archive, &tables.exports)?;
+        self.imports =
+            UnrealArray::<UnrealClassImport>::parse_with_info(&mut self.archive, &tables.imports)?;
+
+        if !self
+            .archive
+            .serialized_with(ObjectVersionUE5::NAMES_REFERENCED_FROM_EXPORT_DATA)
+        {
+            self.names_referenced_from_export_data_count = self.names.len() as i32;
+        }
+        Ok(())
+    }
+}
+
+impl<R> AssetHeader<R> {
+    /// Replace the reader of this header with `f(reader)`, keeping everything that was read so far
+    fn map_reader<T>(self, f: impl FnOnce(R) -> Result<T>) -> Result<AssetHeader<T>> {
+        let Self {
+            archive,
+            total_header_size,
+            package_name,
+            package_flags,
+            names,
+            name_hashes,
             soft_object_paths_count,
             soft_object_paths_offset,
             localization_id,
@@ -588,9 +975,14 @@ where
             soft_package_references_offset,
             searchable_names_offset,
             thumbnail_table_offset,
+            guid,
+            persistent_guid,
+            owner_persistent_guid,
+            generations,
             engine_version,
             compatible_with_engine_version,
             compression_flags,
+            compressed_chunks,
             package_source,
             additional_packages_to_cook,
             texture_allocations,
@@ -603,26 +995,114 @@ where
             names_referenced_from_export_data_count,
             payload_toc_offset,
             data_resource_offset,
+            name_lookup,
+            soft_object_paths,
+        } = self;
+        Ok(AssetHeader {
+            archive: archive.map_reader(f)?,
+            total_header_size,
+            package_name,
+            package_flags,
+            names,
+            name_hashes,
+            soft_object_paths_count,
+            soft_object_paths_offset,
+            localization_id,
+            gatherable_text_data_count,
+            gatherable_text_data_offset,
+            exports,
+            imports,
+            depends_offset,
+            soft_package_references_count,
+            soft_package_references_offset,
+            searchable_names_offset,
+            thumbnail_table_offset,
+            guid,
+            persistent_guid,
+            owner_persistent_guid,
+            generations,
+            engine_version,
+            compatible_with_engine_version,
+            compression_flags,
+            compressed_chunks,
+            package_source,
+            additional_packages_to_cook,
+            texture_allocations,
+            asset_registry_data_offset,
+            bulk_data_start_offset,
+            world_tile_info_data_offset,
+            chunk_ids,
+            preload_dependency_count,
+            preload_dependency_offset,
+            names_referenced_from_export_data_count,
+            payload_toc_offset,
+            data_resource_offset,
+            name_lookup,
+            soft_object_paths,
         })
     }
 }
 
+impl<R> AssetHeader<CompressedPackageReader<R>>
+where
+    R: Seek + Read,
+{
+    /// Parse an [`AssetHeader`] from a package that might have been saved with compressed chunks, which older versions of the engine
+    /// did when cooking with compression. The package is decompressed as it's read, and packages without compressed chunks are read
+    /// as they are.
+    pub fn new_compressed(reader: R) -> Result<Self> {
+        // The package summary isn't compressed, so we can find the compressed chunks before reading anything else
+        let (summary, tables) = AssetHeader::parse_summary(reader)?;
+        let compression_flags = summary.compression_flags;
+        let compressed_chunks = summary.compressed_chunks.clone();
+        let mut header = summary.map_reader(|reader| {
+            CompressedPackageReader::new(reader, compression_flags, compressed_chunks)
+        })?;
+
+        header.read_tables(&tables)?;
+        Ok(header)
+    }
+}
+
+/// Where the tables of a package are stored, as read from its package summary
+struct PackageTables {
+    names: ArrayStreamInfo,
+    exports: ArrayStreamInfo,
+    imports: ArrayStreamInfo,
+}
+
 impl<R> AssetHeader<R> {
+    /// The custom versions this asset was saved with (C++ name: `CustomVersionContainer`)
+    pub fn custom_versions(&self) -> &[CustomVersion] {
+        &self.archive.custom_versions
+    }
+
     /// Attempt to look up `find_name` in the name table serialized in [`AssetHeader::names`], will return None
-    /// if the name does not exist. Names are case insensitive.
+    /// if the name does not exist. Names are case insensitive. The name table is indexed by the first lookup, so changes to
+    /// [`AssetHeader::names`] after that aren't seen.
     pub fn find_name(&self, find_name: &str) -> Option<NameReference> {
         // TODO: Handle `_N` suffixes -> number: Some?
-        let find_name_lower = find_name.to_lowercase();
-        for (index, name) in self.names.iter().enumerate() {
-            if find_name == name || find_name_lower == name.to_lowercase() {
-                return Some(NameReference {
-                    index: index as u32,
-                    number: None,
-                });
+        self.name_lookup().find_name(find_name)
+    }
+
+    /// The index of the name table in [`AssetHeader::names`] that's used by [`AssetHeader::find_name`]
+    pub fn name_lookup(&self) -> &NameLookup {
+        self.name_lookup.get_or_init(|| NameLookup::new(&self.names))
+    }
+
+    /// Check that the hashes stored in the name table match the names, to detect corrupted name tables. Succeeds if the asset
+    /// doesn't have name hashes, see [`AssetHeader::name_hashes`].
+    pub fn verify_name_hashes(&self) -> Result<()> {
+        for (index, (name, hashes)) in self.names.iter().zip(&self.name_hashes).enumerate() {
+            let expected_hashes = NameHashes::new(name);
+            if *hashes != expected_hashes {
+                return Err(Error::InvalidNameHash(format!(
+                    "name {} ({:?}) has hashes {:?}, expected {:?}",
+                    index, name, hashes, expected_hashes
+                )));
             }
         }
-
-        None
+        Ok(())
     }
 
     /// Look up the string representation for a given [`NameReference`].
@@ -630,18 +1110,113 @@ impl<R> AssetHeader<R> {
         &self,
         name_reference: &NameReference,
     ) -> std::result::Result<Cow<'_, str>, InvalidNameIndexError> {
-        let index = name_reference.index as usize;
-        if self.names.len() > index {
-            let mut name = Cow::from(&self.names[index]);
-            if let Some(number) = name_reference.number {
-                name.to_mut().push_str(&format!("_{}", number.get() - 1));
+        resolve_name(&self.names, name_reference)
+    }
+
+    /// Look up the name and outer of the import or export referred to by `reference`
+    fn object_name_and_outer(
+        &self,
+        reference: ObjectReference,
+    ) -> Result<(NameReference, ObjectReference)> {
+        match reference {
+            ObjectReference::Export { export_index } => self
+                .exports
+                .get(export_index)
+                .map(|export| (export.object_name, export.outer())),
+            ObjectReference::Import { import_index } => self
+                .imports
+                .get(import_index)
+                .map(|import| (import.object_name, import.outer())),
+            ObjectReference::None => None,
+        }
+        .ok_or(Error::InvalidObjectReference(reference))
+    }
+
+    /// Look up the name of the class of the import or export referred to by `reference`, e.g. `StaticMesh`. Exports without
+    /// a class are themselves classes, so they resolve to `Class`.
+    pub fn class_name(&self, reference: ObjectReference) -> Result<Cow<'_, str>> {
+        match reference {
+            ObjectReference::Export { export_index } => {
+                let export = self
+                    .exports
+                    .get(export_index)
+                    .ok_or(Error::InvalidObjectReference(reference))?;
+                match export.class() {
+                    ObjectReference::None => Ok(Cow::from("Class")),
+                    class => {
+                        let (class_name, _) = self.object_name_and_outer(class)?;
+                        Ok(self.resolve_name(&class_name)?)
+                    }
+                }
             }
-            Ok(name)
-        } else {
-            Err(InvalidNameIndexError(name_reference.index))
+            ObjectReference::Import { import_index } => {
+                let import = self
+                    .imports
+                    .get(import_index)
+                    .ok_or(Error::InvalidObjectReference(reference))?;
+                Ok(self.resolve_name(&import.class_name)?)
+            }
+            ObjectReference::None => Err(Error::InvalidObjectReference(reference)),
         }
     }
 
+    /// Build the path name of the import or export referred to by `reference` by walking its chain of outers, formatted the same
+    /// way `UObject::GetPathName` does, e.g. `/Game/Maps/MyMap.MyMap:PersistentLevel.MyActor`. Exports without an outer live
+    /// in this package (named by [`AssetHeader::package_name`], which is `None` for assets saved before UE 5.1 unless you fill it in),
+    /// while imports without an outer are packages themselves. Returns an empty string for [`ObjectReference::None`].
+    pub fn object_path(&self, reference: ObjectReference) -> Result<String> {
+        if reference == ObjectReference::None {
+            return Ok(String::new());
+        }
+
+        // Every object appears at most once in a well-formed outer chain, so anything longer than that has to be a cycle
+        let max_chain_length = self.imports.len() + self.exports.len();
+        let mut chain = Vec::new();
+        let mut current = reference;
+        while current != ObjectReference::None {
+            if chain.len() >= max_chain_length {
+                return Err(Error::OuterCycle(reference));
+            }
+            let (object_name, outer) = self.object_name_and_outer(current)?;
+            chain.push((current, object_name));
+            current = outer;
+        }
+
+        let mut path = String::new();
+        let mut depth = 0;
+        if let Some((ObjectReference::Export { .. }, _)) = chain.last() {
+            path.push_str(&self.package_name);
+            depth += 1;
+        }
+
+        for (_, object_name) in chain.iter().rev() {
+            // Objects directly inside a top level asset are separated by `SUBOBJECT_DELIMITER` rather than `.`
+            match depth {
+                0 => {}
+                2 => path.push(':'),
+                _ => path.push('.'),
+            }
+            path.push_str(&self.resolve_name(object_name)?);
+            depth += 1;
+        }
+
+        Ok(path)
+    }
+
+    /// Build the full name of the import or export referred to by `reference`, in the form `Class'/Game/Package.Object'` used
+    /// when exporting object references as text. Returns an empty string for [`ObjectReference::None`].
+    pub fn full_name(&self, reference: ObjectReference) -> Result<String> {
+        if reference == ObjectReference::None {
+            return Ok(String::new());
+        }
+
+        Ok(format!(
+            "{}'{}'",
+            self.class_name(reference)?,
+            self.object_path(reference)?
+        ))
+    }
+
     /// Create an iterator over the names of just the packages imported by this asset (i.e. its dependencies).
     pub fn package_import_iter(&self) -> ImportIterator<'_, R> {
         ImportIterator::new(self)
@@ -652,7 +1227,8 @@ impl<R> AssetHeader<R>
 where
     R: Seek + Read,
 {
-    /// Create an iterator over the names of just the packages imported by this asset (i.e. its dependencies).
+    /// Create an iterator over the thumbnail table, which lists the objects in this asset that have a thumbnail. Use
+    /// [`AssetHeader::read_thumbnail`] to read the images.
     pub fn thumbnail_iter(&mut self) -> Result<UnrealArrayIterator<'_, UnrealThumbnailInfo, R>> {
         self.archive
             .seek(SeekFrom::Start(self.thumbnail_table_offset as u64))?;
@@ -660,4 +1236,430 @@ where
         let stream_info = ArrayStreamInfo::from_current_position(&mut self.archive)?;
         UnrealArrayIterator::new(self, stream_info)
     }
+
+    /// Read the thumbnail image described by an entry from [`AssetHeader::thumbnail_iter`]
+    pub fn read_thumbnail(&mut self, thumbnail_info: &ThumbnailInfo) -> Result<ObjectThumbnail> {
+        self.archive
+            .seek(SeekFrom::Start(thumbnail_info.file_offset as u64))?;
+        UnrealObjectThumbnail::parse_inline(&mut self.archive)
+    }
+
+    /// Read the `DependsMap`, which for each export (in the same order as [`AssetHeader::exports`]) lists the imports and exports
+    /// that it depends on.
+    pub fn depends_map(&mut self) -> Result<Vec<Vec<ObjectReference>>> {
+        // The map has one entry per export, and isn't serialized at all if there are no exports.
+        if self.depends_offset <= 0 {
+            return Ok(Vec::new());
+        }
+
+        let stream_info = ArrayStreamInfo {
+            offset: self.depends_offset as u64,
+            count: self.exports.len() as u64,
+        };
+        UnrealArray::<UnrealArray<UnrealPackageIndex>>::parse_with_info(&mut self.archive, &stream_info)
+    }
+
+    /// Read the names of the packages that this asset references through soft references (e.g. `TSoftObjectPtr`), which are
+    /// not listed in [`AssetHeader::imports`]. (C++ name: `SoftPackageReferenceList`)
+    pub fn soft_package_references(&mut self) -> Result<Vec<String>> {
+        let stream_info = ArrayStreamInfo {
+            offset: self.soft_package_references_offset as u64,
+            count: self.soft_package_references_count.max(0) as u64,
+        };
+        if stream_info.count == 0 {
+            return Ok(Vec::new());
+        }
+
+        if self
+            .archive
+            .serialized_with(ObjectVersion::VER_UE4_ADDED_SOFT_OBJECT_PATH)
+        {
+            let names =
+                UnrealArray::<UnrealNameReference>::parse_with_info(&mut self.archive, &stream_info)?;
+            names
+                .iter()
+                .map(|name| Ok(self.resolve_name(name)?.into_owned()))
+                .collect()
+        } else {
+            // Before `FSoftObjectPath` these were `FStringAssetReference` paths, which may include an object name after the package name
+            let paths = UnrealArray::<UnrealString>::parse_with_info(&mut self.archive, &stream_info)?;
+            let mut package_names: Vec<String> = Vec::with_capacity(paths.len());
+            for path in paths {
+                let package_name = match path.find('.') {
+                    Some(object_name_start) => &path[..object_name_start],
+                    None => &path,
+                };
+                if !package_names.iter().any(|name| name == package_name) {
+                    package_names.push(package_name.to_string());
+                }
+            }
+            Ok(package_names)
+        }
+    }
+
+    /// List the packages that this package references, both through imports and soft references. Packages that are both imported
+    /// and soft referenced are only listed as [`ReferenceKind::Hard`], and references from the package to itself are left out,
+    /// which is why this needs the `package_name` of this package.
+    pub fn package_references(&mut self, package_name: &str) -> Result<Vec<PackageReference>> {
+        let mut references: Vec<PackageReference> = self
+            .package_import_iter()
+            .map(|package_name| PackageReference {
+                package_name,
+                kind: ReferenceKind::Hard,
+            })
+            .collect();
+        for soft_reference in self.soft_package_references()? {
+            if soft_reference != package_name
+                && !references
+                    .iter()
+                    .any(|reference| reference.package_name == soft_reference)
+            {
+                references.push(PackageReference {
+                    package_name: soft_reference,
+                    kind: ReferenceKind::Soft,
+                });
+            }
+        }
+        Ok(references)
+    }
+
+    /// Read the list of soft object paths referenced by this package, which is only serialized by UE 5.1 and later
+    /// (C++ name: `SoftObjectPathList`)
+    pub fn soft_object_paths(&mut self) -> Result<Vec<SoftObjectPath>> {
+        if self.soft_object_paths_count <= 0 {
+            return Ok(Vec::new());
+        }
+
+        let stream_info = ArrayStreamInfo {
+            offset: self.soft_object_paths_offset as u64,
+            count: self.soft_object_paths_count as u64,
+        };
+        let serialized_paths =
+            UnrealArray::<UnrealSoftObjectPath>::parse_with_info(&mut self.archive, &stream_info)?;
+        serialized_paths
+            .iter()
+            .map(|serialized_path| Ok(resolve_soft_object_path(&self.names, serialized_path)?))
+            .collect()
+    }
+
+    /// Read the names that objects in this package reference and want to be searchable without loading the package, like the
+    /// row names of a `FDataTableRowHandle` or the tags of a `FGameplayTagContainer`. (C++ name: `SearchableNamesMap`)
+    pub fn searchable_names(&mut self) -> Result<HashMap<ObjectReference, Vec<String>>> {
+        let searchable_names_offset = match self.searchable_names_offset {
+            Some(offset) if offset > 0 => offset,
+            _ => return Ok(HashMap::new()),
+        };
+
+        self.archive
+            .seek(SeekFrom::Start(searchable_names_offset as u64))?;
+        let count: i32 = self.archive.read_le()?;
+        let mut searchable_names = HashMap::with_capacity(count.clamp(0, 1024) as usize);
+        for _ in 0..count {
+            let object = UnrealPackageIndex::parse_inline(&mut self.archive)?;
+            let names = UnrealArray::<UnrealNameReference>::parse_inline(&mut self.archive)?
+                .iter()
+                .map(|name| Ok(self.resolve_name(name)?.into_owned()))
+                .collect::<Result<_>>()?;
+            searchable_names.insert(object, names);
+        }
+        Ok(searchable_names)
+    }
+
+    /// Read the localizable texts in this package, which the localization pipeline gathers for translation. This is only serialized
+    /// in packages saved for the editor. (C++ name: `GatherableTextDataMap`)
+    pub fn gatherable_text_data(&mut self) -> Result<Vec<GatherableTextData>> {
+        if self.gatherable_text_data_count <= 0 {
+            return Ok(Vec::new());
+        }
+
+        self.archive
+            .seek(SeekFrom::Start(self.gatherable_text_data_offset as u64))?;
+        (0..self.gatherable_text_data_count)
+            .map(|_| GatherableTextData::parse(&mut self.archive))
+            .collect()
+    }
+
+    /// Read the world composition tile info of this map, which is only serialized for maps that are part of a world composition
+    pub fn world_tile_info(&mut self) -> Result<Option<WorldTileInfo>> {
+        let Some(world_tile_info_data_offset) = self.world_tile_info_data_offset else {
+            return Ok(None);
+        };
+
+        self.archive
+            .seek(SeekFrom::Start(world_tile_info_data_offset as u64))?;
+        Ok(Some(WorldTileInfo::parse(&mut self.archive)?))
+    }
+
+    /// Read the asset registry data for this package, i.e. the class and tags of its assets (C++ name: `AssetRegistryData`)
+    pub fn asset_registry_data(&mut self) -> Result<AssetRegistryData> {
+        if self.asset_registry_data_offset <= 0 {
+            return Ok(AssetRegistryData::default());
+        }
+
+        self.archive
+            .seek(SeekFrom::Start(self.asset_registry_data_offset as u64))?;
+
+        // Cooked packages are frozen at the format before `VER_UE4_ASSETREGISTRY_DEPENDENCYFLAGS`, see `ReadPackageDataMain`
+        let has_dependency_data = self
+            .archive
+            .serialized_with(ObjectVersion::VER_UE4_ASSETREGISTRY_DEPENDENCYFLAGS)
+            && !self.package_flags.contains(PackageFlags::FilterEditorOnly);
+        let dependency_data_offset: i64 = if has_dependency_data {
+            self.archive.read_le()?
+        } else {
+            -1
+        };
+
+        let objects = UnrealArray::<UnrealAssetRegistryObject>::parse_inline(&mut self.archive)?;
+
+        let dependency_data = if dependency_data_offset > 0 {
+            self.archive
+                .seek(SeekFrom::Start(dependency_data_offset as u64))?;
+            let import_used_in_game = UnrealBitArray::parse_inline(&mut self.archive)?;
+            let soft_package_used_in_game = UnrealBitArray::parse_inline(&mut self.archive)?;
+            let package_build_dependencies = if self
+                .archive
+                .serialized_with(ObjectVersionUE5::ASSETREGISTRY_PACKAGEBUILDDEPENDENCIES)
+            {
+                UnrealArray::<UnrealPackageBuildDependency>::parse_inline(&mut self.archive)?
+                    .into_iter()
+                    .map(|dependency| {
+                        Ok(PackageBuildDependency {
+                            package_name: self.resolve_name(&dependency.package_name)?.into_owned(),
+                            flags: dependency.flags,
+                        })
+                    })
+                    .collect::<Result<_>>()?
+            } else {
+                Vec::new()
+            };
+
+            Some(AssetRegistryDependencyData {
+                import_used_in_game,
+                soft_package_used_in_game,
+                package_build_dependencies,
+            })
+        } else {
+            None
+        };
+
+        Ok(AssetRegistryData {
+            objects,
+            dependency_data,
+        })
+    }
+
+    /// Read the table describing the bulk data payloads of the objects in this package, which is only serialized with
+    /// [`ObjectVersionUE5::DATA_RESOURCES`] or later (C++ name: `DataResourceMap`)
+    pub fn data_resources(&mut self) -> Result<Vec<ObjectDataResource>> {
+        let Some(data_resource_offset) = self.data_resource_offset else {
+            return Ok(Vec::new());
+        };
+
+        self.archive
+            .seek(SeekFrom::Start(data_resource_offset as u64))?;
+        ObjectDataResource::parse_table(&mut self.archive)
+    }
+
+    /// Read the entries of the data resource table that belong to an export, i.e. the bulk data payloads it owns
+    pub fn export_data_resources(&mut self, export_index: usize) -> Result<Vec<ObjectDataResource>> {
+        if export_index >= self.exports.len() {
+            return Err(Error::InvalidObjectReference(ObjectReference::Export {
+                export_index,
+            }));
+        }
+
+        let owner = ObjectReference::Export { export_index };
+        let mut resources = self.data_resources()?;
+        resources.retain(|resource| resource.outer == owner);
+        Ok(resources)
+    }
+
+    /// Read the preload dependencies of an export, which are only serialized for cooked packages and are empty otherwise
+    /// (C++ name: `PreloadDependencies`)
+    pub fn export_preload_dependencies(&mut self, export_index: usize) -> Result<PreloadDependencies> {
+        let export = self
+            .exports
+            .get(export_index)
+            .ok_or(Error::InvalidObjectReference(ObjectReference::Export {
+                export_index,
+            }))?;
+        if export.first_export_dependency < 0 {
+            return Ok(PreloadDependencies::default());
+        }
+
+        // The dependencies of an export are stored in this order, starting at `first_export_dependency`
+        let counts = [
+            export.serialization_before_serialization_dependencies,
+            export.create_before_serialization_dependencies,
+            export.serialization_before_create_dependencies,
+            export.create_before_create_dependencies,
+        ]
+        .map(|count| count.max(0) as usize);
+        let first_dependency = export.first_export_dependency as usize;
+        let end = first_dependency + counts.iter().sum::<usize>();
+        if self.preload_dependency_offset <= 0 || end > self.preload_dependency_count.max(0) as usize {
+            return Err(Error::InvalidPreloadDependencies(format!(
+                "export {} has dependencies {}..{} but the table has {} entries",
+                export_index, first_dependency, end, self.preload_dependency_count
+            )));
+        }
+
+        self.archive.seek(SeekFrom::Start(
+            self.preload_dependency_offset as u64 + first_dependency as u64 * 4,
+        ))?;
+        let mut dependencies = (first_dependency..end)
+            .map(|_| UnrealPackageIndex::parse_inline(&mut self.archive))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter();
+        let [
+            serialization_before_serialization,
+            create_before_serialization,
+            serialization_before_create,
+            create_before_create,
+        ] = counts.map(|count| dependencies.by_ref().take(count).collect());
+        Ok(PreloadDependencies {
+            serialization_before_serialization,
+            create_before_serialization,
+            serialization_before_create,
+            create_before_create,
+        })
+    }
+
+    /// Read the table of contents of the editor bulk data payloads of this package, which says whether they're stored in the package
+    /// or have been virtualized. It's only serialized with [`ObjectVersionUE5::PAYLOAD_TOC`] or later, and is empty otherwise.
+    pub fn payload_toc(&mut self) -> Result<PayloadToc> {
+        if self.payload_toc_offset <= 0 {
+            return Ok(PayloadToc::default());
+        }
+
+        self.archive
+            .seek(SeekFrom::Start(self.payload_toc_offset as u64))?;
+        PayloadToc::parse(&mut self.archive)
+    }
+
+    /// Read the serialized data of an export (C++ name: `SerialOffset` and `SerialSize`)
+    pub fn export_data(&mut self, export_index: usize) -> Result<Vec<u8>> {
+        let export = self
+            .exports
+            .get(export_index)
+            .ok_or(Error::InvalidObjectReference(ObjectReference::Export {
+                export_index,
+            }))?;
+        let serial_size = export.serial_size as u64;
+
+        self.archive
+            .seek(SeekFrom::Start(export.serial_offset as u64))?;
+        let mut data = Vec::new();
+        (&mut self.archive).take(serial_size).read_to_end(&mut data)?;
+        if data.len() as u64 != serial_size {
+            return Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+        Ok(data)
+    }
+
+    /// Read the header of an `FByteBulkData` at `offset` in the package, which is somewhere in the serialized data of the export that
+    /// owns it (where exactly depends on the class of the export). With [`ObjectVersionUE5::DATA_RESOURCES`] or later the export data
+    /// only holds an index into [`AssetHeader::data_resources`], which the header is built from.
+    pub fn read_bulk_data_header(&mut self, offset: u64) -> Result<BulkDataHeader> {
+        if !self
+            .archive
+            .serialized_with(ObjectVersionUE5::DATA_RESOURCES)
+        {
+            self.archive.seek(SeekFrom::Start(offset))?;
+            return BulkDataHeader::parse(&mut self.archive, self.bulk_data_start_offset);
+        }
+
+        // The header is stored in the data resource table, and the export data just refers to it by index
+        self.archive.seek(SeekFrom::Start(offset))?;
+        let data_resource_index: i32 = self.archive.read_le()?;
+        let inline_offset = self.archive.stream_position()? as i64;
+        let data_resources = self.data_resources()?;
+        let data_resource = usize::try_from(data_resource_index)
+            .ok()
+            .and_then(|index| data_resources.get(index))
+            .ok_or_else(|| {
+                Error::InvalidBulkData(format!(
+                    "invalid data resource index {} in a table of {} entries",
+                    data_resource_index,
+                    data_resources.len()
+                ))
+            })?;
+        Ok(BulkDataHeader::from_data_resource(
+            data_resource,
+            self.bulk_data_start_offset,
+            inline_offset,
+        ))
+    }
+
+    /// Read the payload of bulk data that's stored in the package itself, i.e. [`BulkDataLocation::Inline`] or
+    /// [`BulkDataLocation::EndOfFile`]. Use [`AssetPackage::bulk_data_reader`] to also read payloads stored in separate files.
+    pub fn bulk_data_reader(
+        &mut self,
+        header: &BulkDataHeader,
+    ) -> Result<BulkDataReader<&mut Archive<R>>> {
+        if let BulkDataLocation::SeparateFile(file) = header.location() {
+            return Err(Error::InvalidBulkData(format!(
+                "payload is stored in a separate .{} file",
+                file.extension()
+            )));
+        }
+        header.reader(&mut self.archive)
+    }
+
+    /// Read the tagged properties at the start of the serialized data of an export (see `UObject::SerializeScriptProperties`).
+    /// For exports with other data after their properties (e.g. classes) only the properties are read. Packages with
+    /// [`PackageFlags::UnversionedProperties`] need a schema, see [`AssetHeader::export_properties_with_schemas`].
+    pub fn export_properties(&mut self, export_index: usize) -> Result<Vec<Property>> {
+        self.read_export_properties(export_index, None)
+    }
+
+    /// Read the properties of an export like [`AssetHeader::export_properties`], using `schemas` to look up the properties of the
+    /// export's class if the package uses unversioned properties
+    pub fn export_properties_with_schemas(
+        &mut self,
+        export_index: usize,
+        schemas: &dyn PropertySchemaProvider,
+    ) -> Result<Vec<Property>> {
+        self.read_export_properties(export_index, Some(schemas))
+    }
+
+    fn read_export_properties(
+        &mut self,
+        export_index: usize,
+        schemas: Option<&dyn PropertySchemaProvider>,
+    ) -> Result<Vec<Property>> {
+        let export = self
+            .exports
+            .get(export_index)
+            .ok_or(Error::InvalidObjectReference(ObjectReference::Export {
+                export_index,
+            }))?;
+        let start_offset = export.serial_offset as u64;
+        let end_offset = start_offset + export.serial_size as u64;
+
+        let schemas = if self.package_flags.contains(PackageFlags::UnversionedProperties) {
+            Some(schemas.ok_or_else(|| {
+                Error::InvalidPropertyData(
+                    "unversioned properties can't be read without a schema".to_string(),
+                )
+            })?)
+        } else {
+            None
+        };
+
+        let class_name = self
+            .class_name(ObjectReference::Export { export_index })?
+            .into_owned();
+        if self.soft_object_paths.is_none() {
+            self.soft_object_paths = Some(self.soft_object_paths()?);
+        }
+        let soft_object_paths = self.soft_object_paths.as_deref().unwrap_or_default();
+        self.archive.seek(SeekFrom::Start(start_offset))?;
+        let mut reader = PropertyReader::new(&mut self.archive, &self.names, soft_object_paths);
+        if let Some(schemas) = schemas {
+            reader = reader.with_schemas(schemas);
+        }
+        reader.read_object_properties(&class_name, end_offset)
+    }
 }
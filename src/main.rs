@@ -1,37 +1,26 @@
 use anyhow::{anyhow, bail, ensure, Result};
-use log::{error, trace};
+use log::{error, trace, warn};
 use serde::{Deserialize, Deserializer};
 use simplelog::{Config, TermLogger, TerminalMode};
 use std::{
-    fs::File,
+    fs::{self, File},
     io::BufReader,
     num::NonZeroU32,
     path::{Path, PathBuf},
     time,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use structopt::StructOpt;
 use structopt_flags::LogLevel;
 use tempfile::TempDir;
-use uasset::{AssetHeader, ObjectReference};
+use uasset::{
+    is_uasset, AssetHeader, AssetIndex, GatherableTextData, LocMetadataObject,
+    LocMetadataValue, MountPoints, ObjectFlags, ObjectReference, ReferenceKind,
+};
 use walkdir::WalkDir;
-use uasset::enums::ObjectFlags;
-
-const UASSET_EXTENSIONS: [&str; 2] = ["uasset", "umap"];
-
-fn is_uasset<P: AsRef<Path>>(path: P) -> bool {
-    let path = path.as_ref();
-    if let Some(extension) = path.extension() {
-        let extension = extension.to_string_lossy();
-        UASSET_EXTENSIONS.contains(&extension.as_ref())
-    } else {
-        false
-    }
-}
 
 #[derive(Debug, PartialEq)]
 enum Validation {
-    #[allow(dead_code)]
     AssetReferencesExist,
     HasEngineVersion,
 }
@@ -52,6 +41,20 @@ impl ValidationMode {
     }
 }
 
+#[derive(Debug)]
+enum TextFormat {
+    Json,
+    Po,
+}
+
+fn parse_text_format(src: &str) -> Result<TextFormat> {
+    match src {
+        "json" => Ok(TextFormat::Json),
+        "po" => Ok(TextFormat::Po),
+        _ => bail!("Unrecognized text format {}, expected json or po", src),
+    }
+}
+
 fn parse_validation_mode(src: &str) -> Result<ValidationMode> {
     if src == "All" {
         Ok(ValidationMode::All)
@@ -60,9 +63,8 @@ fn parse_validation_mode(src: &str) -> Result<ValidationMode> {
         let modes = src.split(',');
         let mut parsed_modes = Vec::new();
         for mode in modes {
-            #[allow(clippy::unimplemented)]
             let parsed_mode = match mode {
-                "AssetReferencesExist" => unimplemented!("Validation::AssetReferencesExist"),
+                "AssetReferencesExist" => Validation::AssetReferencesExist,
                 "HasEngineVersion" => Validation::HasEngineVersion,
                 _ => bail!("Unrecognized validation mode {}", mode),
             };
@@ -110,6 +112,10 @@ enum Command {
         ///  - `HasEngineVersion`: Verify that every asset has a valid engine version
         #[structopt(long, parse(try_from_str = parse_validation_mode), verbatim_doc_comment)]
         mode: Option<ValidationMode>,
+        /// Content directory of the project (e.g. `MyProject/Content`) that `/Game/` references are resolved against, required
+        /// for `AssetReferencesExist`. Plugins in the project's `Plugins` directory are mounted as well.
+        #[structopt(long)]
+        content_root: Option<PathBuf>,
     },
     /// Show the imports for the listed assets
     ListImports {
@@ -118,17 +124,65 @@ enum Command {
         /// Skip showing imports for code references (imports that start with /Script/)
         #[structopt(long)]
         skip_code_imports: bool,
+        /// Also show packages that are only referenced through soft references
+        #[structopt(long)]
+        include_soft_references: bool,
     },
     /// Show the object types of the public exports of the listed assets
     ListObjectTypes {
         /// Assets to list object types for, directories will be recursively searched for assets
         assets_or_directories: Vec<PathBuf>,
     },
+    /// Show the packages that reference an asset
+    Referencers {
+        /// Asset to show referencers for, either a path to the asset or a package name like `/Game/Maps/MyMap`
+        asset: String,
+        /// Content directory of the project (e.g. `MyProject/Content`) to search for referencers. Plugins in the project's
+        /// `Plugins` directory are searched as well.
+        #[structopt(long)]
+        content_root: PathBuf,
+        /// Also show packages that only reference the asset through soft references
+        #[structopt(long)]
+        include_soft_references: bool,
+    },
+    /// Show groups of packages that depend on each other in a cycle
+    FindCycles {
+        /// Content directory of the project (e.g. `MyProject/Content`) to search for cycles. Plugins in the project's `Plugins`
+        /// directory are searched as well.
+        content_root: PathBuf,
+        /// Also show cycles that are only formed when following soft references
+        #[structopt(long)]
+        include_soft_references: bool,
+    },
+    /// Show which of the listed assets have editor bulk data payloads stored locally, and which have had them virtualized
+    ListPayloads {
+        /// Assets to list payloads for, directories will be recursively searched for assets
+        assets_or_directories: Vec<PathBuf>,
+    },
     /// Dump some information about the thumbnails for the listed assets
     DumpThumbnailInfo {
         /// Assets to dump thumbnail info for, directories will be recursively searched for assets
         assets_or_directories: Vec<PathBuf>,
     },
+    /// Gather the localizable text of the listed assets, for translation
+    GatherText {
+        /// Assets to gather text from, directories will be recursively searched for assets
+        assets_or_directories: Vec<PathBuf>,
+        /// Output format, either `json` or `po` (a gettext catalog with an empty translation for every text)
+        #[structopt(long, default_value = "json", parse(try_from_str = parse_text_format))]
+        format: TextFormat,
+        /// Leave out text that's only used in the editor, like tooltips and categories
+        #[structopt(long)]
+        skip_editor_only: bool,
+    },
+    /// Write the thumbnails of the listed assets to image files, named after the asset and the object they're for
+    ExtractThumbnails {
+        /// Assets to extract thumbnails from, directories will be recursively searched for assets
+        assets_or_directories: Vec<PathBuf>,
+        /// Directory to write the images to, which is created if needed
+        #[structopt(long)]
+        out: PathBuf,
+    },
 }
 
 fn recursively_walk_uassets(paths: Vec<PathBuf>) -> Vec<PathBuf> {
@@ -156,6 +210,44 @@ fn recursively_walk_uassets(paths: Vec<PathBuf>) -> Vec<PathBuf> {
         .collect()
 }
 
+/// Mount `content_root` as `/Game/`, and every plugin with a `Content` directory under `content_root/../Plugins` as `/PluginName/`
+fn discover_mount_points(content_root: &Path) -> Result<MountPoints> {
+    let mut mount_points = MountPoints::new();
+    mount_points.mount("/Game/", content_root)?;
+
+    if let Some(plugins_root) = content_root.canonicalize()?.parent().map(|p| p.join("Plugins")) {
+        let plugin_descriptors = WalkDir::new(plugins_root)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|e| e == "uplugin"));
+        for plugin_descriptor in plugin_descriptors {
+            let plugin_path = plugin_descriptor.path();
+            let plugin_content = plugin_path.with_file_name("Content");
+            if let (Some(plugin_name), true) = (plugin_path.file_stem(), plugin_content.is_dir()) {
+                trace!("mounting {} at /{}/", plugin_content.display(), plugin_name.to_string_lossy());
+                mount_points.mount(&format!("/{}/", plugin_name.to_string_lossy()), plugin_content)?;
+            }
+        }
+    }
+
+    Ok(mount_points)
+}
+
+/// The directories that are mounted in `mount_points`
+fn content_directories(mount_points: &MountPoints) -> Vec<PathBuf> {
+    mount_points.mounts().map(|(_, directory)| directory.to_path_buf()).collect()
+}
+
+/// Index all the assets in the mounted directories, logging any that can't be read
+fn build_index(mount_points: &MountPoints) -> AssetIndex {
+    let (index, failures) = AssetIndex::scan(mount_points);
+    for (asset_path, error) in failures {
+        error!("failed to index {}: {:?}", asset_path.display(), error);
+    }
+    index
+}
+
 #[derive(Debug)]
 enum PerforceAction {
     Add,
@@ -210,9 +302,45 @@ struct PerforceFilesRecord {
     pub file_type: String,
 }
 
-fn fetch_perforce_uassets(changelist: NonZeroU32) -> Result<(Option<TempDir>, Vec<PathBuf>)> {
+#[derive(Deserialize)]
+#[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
+struct PerforceWhereRecord {
+    pub depot_file: String,
+    pub client_file: String,
+    pub path: String,
+}
+
+/// Look up where a depot file lives in the local workspace
+fn perforce_local_path(depot_file: &str) -> Result<PathBuf> {
+    let command = std::process::Command::new("p4")
+        .args(["-z", "tag", "-Mj"])
+        .arg("where")
+        .arg(depot_file)
+        .output()?;
+
+    ensure!(
+        command.status.success(),
+        "Failed to run `p4 where {}`",
+        depot_file
+    );
+
+    let stdout = std::str::from_utf8(&command.stdout)?;
+    let record: PerforceWhereRecord = serde_json::from_str(stdout.trim())?;
+    Ok(PathBuf::from(record.path))
+}
+
+/// The path to read an asset from, and the path of the asset in the project, which differ for assets fetched from Perforce
+type AssetLocation = (PathBuf, PathBuf);
+
+/// Fetch the uassets modified in `changelist` into a temporary directory, returning the path of each fetched uasset along with
+/// its path in the local workspace. Also returns the local workspace paths of any uassets that are deleted in the changelist.
+fn fetch_perforce_uassets(
+    changelist: NonZeroU32,
+) -> Result<(Option<TempDir>, Vec<AssetLocation>, Vec<PathBuf>)> {
     let asset_dir = TempDir::new()?;
     let mut asset_paths = Vec::new();
+    let mut deleted_asset_paths = Vec::new();
 
     let command = std::process::Command::new("p4")
         .args(["-z", "tag", "-Mj"])
@@ -266,7 +394,14 @@ fn fetch_perforce_uassets(changelist: NonZeroU32) -> Result<(Option<TempDir>, Ve
                 filespec
             );
 
-            asset_paths.push(local_path);
+            asset_paths.push((local_path, perforce_local_path(&record.depot_file)?));
+        } else if is_uasset(&record.depot_file) {
+            trace!(
+                "file {} is being removed with action {:?}",
+                record.depot_file,
+                record.action
+            );
+            deleted_asset_paths.push(perforce_local_path(&record.depot_file)?);
         } else {
             trace!(
                 "ignoring file {} with non-modification action {:?}",
@@ -277,9 +412,9 @@ fn fetch_perforce_uassets(changelist: NonZeroU32) -> Result<(Option<TempDir>, Ve
     }
 
     if asset_paths.is_empty() {
-        Ok((None, asset_paths))
+        Ok((None, asset_paths, deleted_asset_paths))
     } else {
-        Ok((Some(asset_dir), asset_paths))
+        Ok((Some(asset_dir), asset_paths, deleted_asset_paths))
     }
 }
 
@@ -325,6 +460,109 @@ fn try_parse_or_log<T: FnOnce(AssetHeader<BufReader<File>>)>(
     }
 }
 
+/// Convert localization metadata to JSON, which it's modeled after
+fn metadata_to_json(metadata: &LocMetadataObject) -> serde_json::Value {
+    fn value_to_json(value: &LocMetadataValue) -> serde_json::Value {
+        match value {
+            LocMetadataValue::Null => serde_json::Value::Null,
+            LocMetadataValue::String(string) => serde_json::Value::from(string.as_str()),
+            LocMetadataValue::Boolean(boolean) => serde_json::Value::from(*boolean),
+            LocMetadataValue::Array(values) => values.iter().map(value_to_json).collect(),
+            LocMetadataValue::Object(object) => metadata_to_json(object),
+        }
+    }
+
+    metadata.iter().map(|(key, value)| (key.clone(), value_to_json(value))).collect::<serde_json::Map<_, _>>().into()
+}
+
+/// Quote a string for a PO file
+fn po_string(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for ch in string.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A text to translate in a PO file, which might be used in several places and packages
+struct PoMessage {
+    namespace: String,
+    key: String,
+    source_string: String,
+    site_descriptions: Vec<String>,
+    localization_ids: Vec<String>,
+}
+
+/// Write the gathered texts as a PO file, using the same `msgctxt` of `Namespace,Key` as the editor's PO export
+fn print_po(gathered_texts: &[(PathBuf, Option<String>, Vec<GatherableTextData>)]) {
+    let mut messages: Vec<PoMessage> = Vec::new();
+    let mut message_indices: HashMap<(String, String), usize> = HashMap::new();
+    for (asset_path, localization_id, texts) in gathered_texts {
+        for text in texts {
+            for site in &text.source_site_contexts {
+                let message_index = *message_indices.entry((text.namespace.clone(), site.key.clone())).or_insert_with(|| {
+                    messages.push(PoMessage {
+                        namespace: text.namespace.clone(),
+                        key: site.key.clone(),
+                        source_string: text.source_string.clone(),
+                        site_descriptions: Vec::new(),
+                        localization_ids: Vec::new(),
+                    });
+                    messages.len() - 1
+                });
+
+                let message = &mut messages[message_index];
+                if message.source_string != text.source_string {
+                    warn!(
+                        "{}: text {:?} in namespace {:?} is {:?}, but it's {:?} elsewhere, ignoring it",
+                        asset_path.display(),
+                        site.key,
+                        text.namespace,
+                        text.source_string,
+                        message.source_string
+                    );
+                    continue;
+                }
+                message.site_descriptions.push(site.site_description.clone());
+                if let Some(localization_id) = localization_id
+                    && !message.localization_ids.contains(localization_id)
+                {
+                    message.localization_ids.push(localization_id.clone());
+                }
+            }
+        }
+    }
+
+    println!("msgid \"\"");
+    println!("msgstr \"\"");
+    println!("\"Content-Type: text/plain; charset=UTF-8\\n\"");
+    for message in messages {
+        println!();
+        println!("#. Key:\t{}", message.key);
+        for localization_id in &message.localization_ids {
+            println!("#. PackageLocalizationId:\t{}", localization_id);
+        }
+        for site_description in &message.site_descriptions {
+            println!("#. SourceLocation:\t{}", site_description);
+        }
+        for site_description in &message.site_descriptions {
+            println!("#: {}", site_description);
+        }
+        println!("msgctxt {}", po_string(&format!("{},{}", message.namespace, message.key)));
+        println!("msgid {}", po_string(&message.source_string));
+        println!("msgstr \"\"");
+    }
+}
+
 fn main() -> Result<()> {
     let options = CommandOptions::from_args();
     TermLogger::init(
@@ -386,44 +624,163 @@ fn main() -> Result<()> {
             assets_or_directories,
             mode,
             perforce_changelist,
+            content_root,
         } => {
             let mode = mode.unwrap_or(ValidationMode::All);
             let mut errors = Vec::new();
-            let (temp_dir, asset_paths) = {
-                let mut asset_paths = recursively_walk_uassets(assets_or_directories);
+            let (temp_dir, asset_paths, mut deleted_asset_paths) = {
+                let mut asset_paths: Vec<AssetLocation> = recursively_walk_uassets(assets_or_directories)
+                    .into_iter()
+                    .map(|path| (path.clone(), path))
+                    .collect();
                 if let Some(changelist) = perforce_changelist {
-                    let (asset_dir, mut assets) = fetch_perforce_uassets(changelist)?;
+                    let (asset_dir, mut assets, deleted_assets) = fetch_perforce_uassets(changelist)?;
                     asset_paths.append(&mut assets);
-                    (asset_dir, asset_paths)
+                    (asset_dir, asset_paths, deleted_assets)
                 } else {
-                    (None, asset_paths)
+                    (None, asset_paths, Vec::new())
+                }
+            };
+
+            let mount_points = match content_root {
+                Some(content_root) if mode.includes(&Validation::AssetReferencesExist) => {
+                    Some(discover_mount_points(&content_root)?)
+                }
+                None if mode.includes(&Validation::AssetReferencesExist) => {
+                    ensure!(
+                        matches!(mode, ValidationMode::All),
+                        "AssetReferencesExist requires --content-root"
+                    );
+                    warn!("skipping AssetReferencesExist, as it requires --content-root");
+                    None
                 }
+                _ => None,
+            };
+
+            // Listed assets that no longer exist on disk are treated as being deleted, just like deletions in the changelist
+            let asset_paths: Vec<AssetLocation> = if mount_points.is_some() {
+                let (existing_asset_paths, missing_asset_paths): (Vec<_>, Vec<_>) =
+                    asset_paths.into_iter().partition(|(path, _)| path.exists());
+                deleted_asset_paths.extend(missing_asset_paths.into_iter().map(|(_, project_path)| project_path));
+                existing_asset_paths
+            } else {
+                asset_paths
+            };
+
+            let deleted_packages: HashSet<String> = match &mount_points {
+                Some(mount_points) => deleted_asset_paths
+                    .iter()
+                    .filter_map(|path| {
+                        let package_name = mount_points.package_name(path);
+                        if package_name.is_none() {
+                            error!("{} is not in the content root or a plugin, ignoring its deletion", path.display());
+                        }
+                        package_name
+                    })
+                    .collect(),
+                None => HashSet::new(),
             };
 
             let mut num_evaluated_assets = 0;
-            for asset_path in asset_paths {
+            // Packages we've already validated, so we don't check the (possibly outdated) copies in the project again
+            let mut validated_packages = HashSet::new();
+            for (asset_path, project_path) in asset_paths {
                 num_evaluated_assets += 1;
                 match try_parse(&asset_path) {
-                    Ok(header) => {
+                    Ok(mut header) => {
                         if header.engine_version.is_empty()
                             && mode.includes(&Validation::HasEngineVersion)
                         {
                             errors.push(format!(
                                 "{}: Missing engine version, resave with a versioned editor",
-                                asset_path.display()
+                                project_path.display()
                             ));
                         }
+
+                        if let Some(mount_points) = &mount_points {
+                            let package_name =
+                                mount_points.package_name(&project_path).unwrap_or_else(|| header.package_name.clone());
+                            let missing_references =
+                                mount_points.missing_references(&package_name, &mut header, &deleted_packages);
+                            validated_packages.insert(package_name);
+                            match missing_references {
+                                Ok(missing_references) => {
+                                    errors.extend(missing_references.into_iter().map(|reference| {
+                                        let kind = match reference.kind {
+                                            ReferenceKind::Hard => "Import",
+                                            ReferenceKind::Soft => "Soft reference",
+                                        };
+                                        format!(
+                                            "{}: {} {} does not exist",
+                                            project_path.display(),
+                                            kind,
+                                            reference.package_name
+                                        )
+                                    }));
+                                }
+                                Err(error) => errors.push(format!(
+                                    "{}: Could not read package references: {}",
+                                    project_path.display(),
+                                    error
+                                )),
+                            }
+                        }
                     }
                     Err(error) => {
                         errors.push(format!(
                             "{}: Could not parse asset: {}",
-                            asset_path.display(),
+                            project_path.display(),
                             error
                         ));
                     }
                 };
             }
 
+            // Any package in the project could be referencing the ones being deleted, so we need to look at all of them
+            if let (Some(mount_points), false) = (&mount_points, deleted_packages.is_empty()) {
+                let project_asset_paths = recursively_walk_uassets(content_directories(mount_points));
+                for asset_path in project_asset_paths {
+                    let Some(package_name) = mount_points.package_name(&asset_path) else {
+                        continue;
+                    };
+                    if validated_packages.contains(&package_name) || deleted_packages.contains(&package_name) {
+                        continue;
+                    }
+
+                    // An asset we can't read could be referencing the deleted packages, so it fails validation
+                    match try_parse(&asset_path) {
+                        Ok(mut header) => {
+                            num_evaluated_assets += 1;
+                            match header.package_references(&package_name) {
+                                Ok(references) => {
+                                    for reference in references {
+                                        if deleted_packages.contains(&reference.package_name) {
+                                            errors.push(format!(
+                                                "{}: References {}, which is being deleted",
+                                                asset_path.display(),
+                                                reference.package_name
+                                            ));
+                                        }
+                                    }
+                                }
+                                Err(error) => errors.push(format!(
+                                    "{}: Could not read package references: {}",
+                                    asset_path.display(),
+                                    error
+                                )),
+                            }
+                        }
+                        Err(error) => {
+                            errors.push(format!(
+                                "{}: Could not parse asset: {}",
+                                asset_path.display(),
+                                error
+                            ));
+                        }
+                    }
+                }
+            }
+
             if let Some(temp_dir) = temp_dir {
                 temp_dir.close()?;
             }
@@ -445,12 +802,30 @@ fn main() -> Result<()> {
         Command::ListImports {
             assets_or_directories,
             skip_code_imports,
+            include_soft_references,
         } => {
             let asset_paths = recursively_walk_uassets(assets_or_directories);
             for asset_path in asset_paths {
-                try_parse_or_log(&asset_path, |header| {
+                try_parse_or_log(&asset_path, |mut header| {
                     println!("{}:", asset_path.display());
-                    for import in header.package_import_iter() {
+                    let package_name = header.package_name.clone();
+                    let references = match header.package_references(&package_name) {
+                        Ok(references) => references,
+                        Err(error) => {
+                            error!(
+                                "failed to read package references for {}: {:?}",
+                                asset_path.display(),
+                                error
+                            );
+                            return;
+                        }
+                    };
+                    let imports = references
+                        .into_iter()
+                        .filter(|reference| include_soft_references || reference.kind == ReferenceKind::Hard)
+                        .map(|reference| reference.package_name);
+
+                    for import in imports {
                         if !skip_code_imports || !import.starts_with("/Script/") {
                             println!("  {}", import);
                         }
@@ -461,8 +836,8 @@ fn main() -> Result<()> {
         Command::ListObjectTypes {
             assets_or_directories,
         } => {
-            let checked_flags = ObjectFlags::Standalone as u32 | ObjectFlags::Public as u32 | ObjectFlags::Transient as u32 | ObjectFlags::ClassDefaultObject as u32;
-            let expected_flags = ObjectFlags::Standalone as u32 | ObjectFlags::Public as u32;
+            let checked_flags = ObjectFlags::Standalone | ObjectFlags::Public | ObjectFlags::Transient | ObjectFlags::ClassDefaultObject;
+            let expected_flags = ObjectFlags::Standalone | ObjectFlags::Public;
             let asset_paths = recursively_walk_uassets(assets_or_directories);
             let mut asset_types = HashMap::new();
             for asset_path in asset_paths {
@@ -471,15 +846,9 @@ fn main() -> Result<()> {
                     let expected_object_name = header.package_name[expected_object_name_start_index..].to_string();
                     let expected_object_name_index = header.find_name(&expected_object_name);
 
-                    let asset_object = header.exports.iter().find(|export| Some(export.object_name) == expected_object_name_index && export.is_asset && export.object_flags & checked_flags == expected_flags);
-                    let asset_type = asset_object.and_then(|asset_object| {
-                        let class_name = match asset_object.class() {
-                            ObjectReference::Export { export_index } => header.exports.get(export_index).map(|e| e.object_name),
-                            ObjectReference::Import { import_index } => header.imports.get(import_index).map(|e| e.object_name),
-                            ObjectReference::None => None,
-                        };
-
-                        class_name.and_then(|name| header.resolve_name(&name).map(|s| s.to_string()).ok())
+                    let asset_index = header.exports.iter().position(|export| Some(export.object_name) == expected_object_name_index && export.is_asset && export.object_flags & checked_flags == expected_flags);
+                    let asset_type = asset_index.and_then(|export_index| {
+                        header.class_name(ObjectReference::Export { export_index }).map(|s| s.to_string()).ok()
                     });
 
                     asset_types.insert(asset_path.display().to_string(), asset_type);
@@ -487,6 +856,84 @@ fn main() -> Result<()> {
             }
             println!("{json}", json = serde_json::to_string(&asset_types)?);
         }
+        Command::Referencers {
+            asset,
+            content_root,
+            include_soft_references,
+        } => {
+            let mount_points = discover_mount_points(&content_root)?;
+            let package_name = if asset.starts_with('/') && !Path::new(&asset).exists() {
+                asset
+            } else {
+                mount_points.package_name(Path::new(&asset)).ok_or_else(|| {
+                    anyhow!("{} is not in the content root or a plugin", asset)
+                })?
+            };
+
+            let index = build_index(&mount_points);
+            println!("{}:", package_name);
+            for referencer in index.referencers(&package_name) {
+                if include_soft_references || referencer.kind == ReferenceKind::Hard {
+                    println!("  {}", referencer.package_name);
+                }
+            }
+        }
+        Command::FindCycles {
+            content_root,
+            include_soft_references,
+        } => {
+            let index = build_index(&discover_mount_points(&content_root)?);
+            let hard_cycles = index.find_cycles(false);
+            for cycle in &hard_cycles {
+                println!("Cycle:");
+                for package_name in cycle {
+                    println!("  {}", package_name);
+                }
+            }
+
+            if include_soft_references {
+                for cycle in index.find_cycles(true) {
+                    if !hard_cycles.contains(&cycle) {
+                        println!("Cycle (including soft references):");
+                        for package_name in cycle {
+                            println!("  {}", package_name);
+                        }
+                    }
+                }
+            }
+        }
+        Command::ListPayloads {
+            assets_or_directories,
+        } => {
+            let asset_paths = recursively_walk_uassets(assets_or_directories);
+            for asset_path in asset_paths {
+                try_parse_or_log(&asset_path, |mut header| match header.payload_toc() {
+                    Ok(payload_toc) if payload_toc.entries.is_empty() => {}
+                    Ok(payload_toc) => {
+                        println!(
+                            "{}: {} local, {} virtualized",
+                            asset_path.display(),
+                            payload_toc.local_payloads().count(),
+                            payload_toc.virtualized_payloads().count()
+                        );
+                        for payload in &payload_toc.entries {
+                            println!(
+                                "  {} {:?} ({} bytes, {} on disk)",
+                                payload.identifier,
+                                payload.access_mode,
+                                payload.raw_size,
+                                payload.compressed_size
+                            );
+                        }
+                    }
+                    Err(error) => error!(
+                        "failed to read payloads for {}: {:?}",
+                        asset_path.display(),
+                        error
+                    ),
+                });
+            }
+        }
         Command::DumpThumbnailInfo {
             assets_or_directories,
         } => {
@@ -519,6 +966,120 @@ fn main() -> Result<()> {
                 });
             }
         }
+        Command::GatherText {
+            assets_or_directories,
+            format,
+            skip_editor_only,
+        } => {
+            let asset_paths = recursively_walk_uassets(assets_or_directories);
+            let mut gathered_texts = Vec::new();
+            for asset_path in asset_paths {
+                try_parse_or_log(&asset_path, |mut header| match header.gatherable_text_data() {
+                    Ok(mut texts) => {
+                        if skip_editor_only {
+                            for text in &mut texts {
+                                text.source_site_contexts.retain(|site| !site.is_editor_only);
+                            }
+                            texts.retain(|text| !text.source_site_contexts.is_empty());
+                        }
+                        if !texts.is_empty() {
+                            gathered_texts.push((asset_path.clone(), header.localization_id.clone(), texts));
+                        }
+                    }
+                    Err(error) => error!("failed to gather text from {}: {:?}", asset_path.display(), error),
+                });
+            }
+
+            match format {
+                TextFormat::Json => {
+                    let json: Vec<serde_json::Value> = gathered_texts
+                        .iter()
+                        .map(|(asset_path, localization_id, texts)| {
+                            let texts: Vec<serde_json::Value> = texts
+                                .iter()
+                                .map(|text| {
+                                    let sites: Vec<serde_json::Value> = text
+                                        .source_site_contexts
+                                        .iter()
+                                        .map(|site| {
+                                            serde_json::json!({
+                                                "key": site.key,
+                                                "path": site.site_description,
+                                                "editor_only": site.is_editor_only,
+                                                "optional": site.is_optional,
+                                                "info_metadata": metadata_to_json(&site.info_metadata),
+                                                "key_metadata": metadata_to_json(&site.key_metadata),
+                                            })
+                                        })
+                                        .collect();
+                                    serde_json::json!({
+                                        "namespace": text.namespace,
+                                        "source": text.source_string,
+                                        "source_metadata": metadata_to_json(&text.source_string_metadata),
+                                        "sites": sites,
+                                    })
+                                })
+                                .collect();
+                            serde_json::json!({
+                                "asset": asset_path.display().to_string(),
+                                "localization_id": localization_id,
+                                "texts": texts,
+                            })
+                        })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                }
+                TextFormat::Po => print_po(&gathered_texts),
+            }
+        }
+        Command::ExtractThumbnails {
+            assets_or_directories,
+            out,
+        } => {
+            fs::create_dir_all(&out)?;
+            let asset_paths = recursively_walk_uassets(assets_or_directories);
+            for asset_path in asset_paths {
+                try_parse_or_log(&asset_path, |mut header| {
+                    let thumbnail_infos = match header.thumbnail_iter().and_then(|thumbnail_iter| thumbnail_iter.collect::<uasset::Result<Vec<_>>>()) {
+                        Ok(thumbnail_infos) => thumbnail_infos,
+                        Err(error) => {
+                            error!("failed to read thumbnails for {}: {:?}", asset_path.display(), error);
+                            return;
+                        }
+                    };
+
+                    let asset_name = asset_path.file_stem().unwrap_or_default().to_string_lossy();
+                    for thumbnail_info in thumbnail_infos {
+                        let thumbnail = match header.read_thumbnail(&thumbnail_info) {
+                            Ok(thumbnail) if thumbnail.is_empty() => continue,
+                            Ok(thumbnail) => thumbnail,
+                            Err(error) => {
+                                error!(
+                                    "failed to read the thumbnail of {} in {}: {:?}",
+                                    thumbnail_info.object_path_without_package_name,
+                                    asset_path.display(),
+                                    error
+                                );
+                                continue;
+                            }
+                        };
+
+                        // Subobject paths are separated by `:`, which isn't allowed in file names on Windows
+                        let file_name = format!(
+                            "{}.{}.{}",
+                            asset_name,
+                            thumbnail_info.object_path_without_package_name.replace(':', "_"),
+                            thumbnail.format.extension()
+                        );
+                        let image_path = out.join(file_name);
+                        match fs::write(&image_path, &thumbnail.compressed_image_data) {
+                            Ok(()) => println!("{} ({}x{})", image_path.display(), thumbnail.width, thumbnail.height),
+                            Err(error) => error!("failed to write {}: {:?}", image_path.display(), error),
+                        }
+                    }
+                });
+            }
+        }
     }
 
     Ok(())
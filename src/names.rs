@@ -0,0 +1,133 @@
+use crate::NameReference;
+use std::collections::HashMap;
+
+/// The hashes that are stored with each entry in the name table (C++ name: `NonCasePreservingHash` and `CasePreservingHash` in
+/// `FNameEntrySerialized`). These are the low 16 bits of CRC based hashes of the name, which are unrelated to the `CityHash` based
+/// hashes the engine uses for names in memory, and they're not used when loading packages, so they're mostly useful for detecting
+/// corrupted name tables.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct NameHashes {
+    /// Hash of the uppercased name (see `FCrc::Strihash_DEPRECATED`)
+    pub non_case_preserving: u16,
+    /// Hash of the name as is (see `FCrc::StrCrc32`)
+    pub case_preserving: u16,
+}
+
+impl NameHashes {
+    /// Calculate the hashes the engine stores for `name`
+    pub fn new(name: &str) -> Self {
+        Self {
+            non_case_preserving: strihash_deprecated(name) as u16,
+            case_preserving: str_crc32(name) as u16,
+        }
+    }
+}
+
+/// Table for the MSB-first CRC-32 with polynomial 0x04C11DB7 (C++ name: `FCrc::CRCTable_DEPRECATED`)
+const CRC_TABLE_DEPRECATED: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < table.len() {
+        let mut crc = (index as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
+
+/// Table for the standard (reflected) CRC-32 (C++ name: `FCrc::CRCTablesSB8[0]`)
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < table.len() {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
+
+/// Case insensitive hash of a name, which mixes an MSB-first CRC table into an LSB-first CRC (C++ name:
+/// `FCrc::Strihash_DEPRECATED`). Only ASCII characters are uppercased, like `TChar::ToUpper`.
+fn strihash_deprecated(name: &str) -> u32 {
+    let update = |hash: u32, byte: u8| {
+        (hash >> 8) ^ CRC_TABLE_DEPRECATED[((hash ^ byte as u32) & 0xFF) as usize]
+    };
+    // Names that only contain ASCII are stored as ANSI strings by the engine, and hash one byte per character instead of two
+    if name.is_ascii() {
+        name.bytes()
+            .fold(0, |hash, byte| update(hash, byte.to_ascii_uppercase()))
+    } else {
+        name.encode_utf16().fold(0, |hash, character| {
+            let character = if character < 0x80 {
+                (character as u8).to_ascii_uppercase() as u16
+            } else {
+                character
+            };
+            let [low, high] = character.to_le_bytes();
+            update(update(hash, low), high)
+        })
+    }
+}
+
+/// Case sensitive CRC-32 of a name, which hashes every character as 4 bytes regardless of how wide it is (C++ name:
+/// `FCrc::StrCrc32`)
+fn str_crc32(name: &str) -> u32 {
+    let crc = name.encode_utf16().fold(!0, |crc, character| {
+        (character as u32)
+            .to_le_bytes()
+            .iter()
+            .fold(crc, |crc, &byte| {
+                (crc >> 8) ^ CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize]
+            })
+    });
+    !crc
+}
+
+/// An index of a name table for looking up names without scanning the whole table, see [`NameLookup::find_name`]. An
+/// [`crate::AssetHeader`] indexes its names on the first lookup, see [`crate::AssetHeader::name_lookup`].
+#[derive(Clone, Debug, Default)]
+pub struct NameLookup {
+    /// The index of the first name in the table with a given lowercased name
+    indices: HashMap<String, u32>,
+}
+
+impl NameLookup {
+    /// Index the names in `names`
+    pub fn new(names: &[String]) -> Self {
+        let mut indices = HashMap::with_capacity(names.len());
+        for (index, name) in names.iter().enumerate() {
+            indices.entry(name.to_lowercase()).or_insert(index as u32);
+        }
+        Self { indices }
+    }
+
+    /// Look up `find_name` in the indexed names, will return None if the name does not exist. Names are case insensitive, and
+    /// this returns the first matching name in the table.
+    pub fn find_name(&self, find_name: &str) -> Option<NameReference> {
+        self.indices
+            .get(&find_name.to_lowercase())
+            .map(|&index| NameReference {
+                index,
+                number: None,
+            })
+    }
+}
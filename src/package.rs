@@ -0,0 +1,217 @@
+use crate::{AssetHeader, BulkDataHeader, BulkDataLocation, Error, Result};
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+};
+
+/// Extension of the file that holds the export data of a cooked package, next to its `.uasset` or `.umap`
+const UEXP_EXTENSION: &str = "uexp";
+
+/// A reader that presents a package's header and export data as a single stream, even if the export data is stored in a separate
+/// reader. The export data is placed right after the header, which is where export offsets expect it to be.
+#[derive(Debug)]
+pub struct PackageReader<R> {
+    header: R,
+    exports: Option<R>,
+    /// Where the export data starts in the combined stream, i.e. the size of the header if the export data is split out
+    exports_offset: u64,
+    /// Our position in the combined stream
+    position: u64,
+    /// Last known positions of the underlying readers, so we only seek them when needed (which would discard their buffers)
+    header_position: Option<u64>,
+    exports_position: Option<u64>,
+}
+
+impl<R> PackageReader<R>
+where
+    R: Seek + Read,
+{
+    fn new(header: R) -> Self {
+        Self {
+            header,
+            exports: None,
+            exports_offset: u64::MAX,
+            position: 0,
+            header_position: None,
+            exports_position: None,
+        }
+    }
+
+    fn set_exports(&mut self, exports: R, exports_offset: u64) {
+        self.exports = Some(exports);
+        self.exports_offset = exports_offset;
+        self.exports_position = None;
+    }
+
+    /// True if the export data comes from a separate reader
+    pub fn has_split_exports(&self) -> bool {
+        self.exports.is_some()
+    }
+}
+
+impl<R> Read for PackageReader<R>
+where
+    R: Seek + Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = match &mut self.exports {
+            Some(exports) if self.position >= self.exports_offset => {
+                let exports_position = self.position - self.exports_offset;
+                if self.exports_position != Some(exports_position) {
+                    exports.seek(SeekFrom::Start(exports_position))?;
+                }
+                let bytes_read = exports.read(buf)?;
+                self.exports_position = Some(exports_position + bytes_read as u64);
+                bytes_read
+            }
+            _ => {
+                if self.header_position != Some(self.position) {
+                    self.header.seek(SeekFrom::Start(self.position))?;
+                }
+                // Don't read past the end of the header into whatever follows it in the header reader
+                let remaining_header = self.exports_offset - self.position;
+                let buf_len = buf
+                    .len()
+                    .min(remaining_header.try_into().unwrap_or(usize::MAX));
+                let bytes_read = self.header.read(&mut buf[..buf_len])?;
+                self.header_position = Some(self.position + bytes_read as u64);
+                bytes_read
+            }
+        };
+
+        self.position += bytes_read as u64;
+        Ok(bytes_read)
+    }
+}
+
+impl<R> Seek for PackageReader<R>
+where
+    R: Seek + Read,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(position) => (position, 0),
+            SeekFrom::Current(offset) => (self.position, offset),
+            SeekFrom::End(offset) => {
+                let end = if let Some(exports) = &mut self.exports {
+                    let exports_len = exports.seek(SeekFrom::End(0))?;
+                    self.exports_position = Some(exports_len);
+                    self.exports_offset + exports_len
+                } else {
+                    let header_len = self.header.seek(SeekFrom::End(0))?;
+                    self.header_position = Some(header_len);
+                    header_len
+                };
+                (end, offset)
+            }
+        };
+
+        self.position = base.checked_add_signed(offset).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+/// A package whose export data might be stored separately from its header, like cooked content which is split into a `.uasset`
+/// (or `.umap`) and a `.uexp`. Export offsets are relative to the start of the header as if the two were a single file, so the
+/// [`AssetHeader`] this dereferences to can read exports the same way for both editor and cooked content.
+#[derive(Debug)]
+pub struct AssetPackage<R> {
+    header: AssetHeader<PackageReader<R>>,
+    /// Where the package was opened from, used to find files next to it
+    path: Option<PathBuf>,
+}
+
+impl AssetPackage<BufReader<File>> {
+    /// Open the asset at `path`, along with the `.uexp` next to it if there is one
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let header_reader = BufReader::new(File::open(path)?);
+
+        let exports_path = path.with_extension(UEXP_EXTENSION);
+        let export_reader = if exports_path.is_file() {
+            Some(BufReader::new(File::open(exports_path)?))
+        } else {
+            None
+        };
+
+        let mut package = Self::new(header_reader, export_reader)?;
+        package.path = Some(path.to_path_buf());
+        Ok(package)
+    }
+}
+
+impl<R> AssetPackage<R>
+where
+    R: Seek + Read,
+{
+    /// Parse a package from `header_reader`, with its export data in `export_reader` if it's been split out
+    pub fn new(header_reader: R, export_reader: Option<R>) -> Result<Self> {
+        let mut header = AssetHeader::new(PackageReader::new(header_reader))?;
+        if let Some(export_reader) = export_reader {
+            header
+                .archive
+                .reader
+                .set_exports(export_reader, header.total_header_size as u64);
+        }
+        Ok(Self { header, path: None })
+    }
+
+    /// True if the export data is stored separately from the header
+    pub fn has_split_exports(&self) -> bool {
+        self.header.archive.reader.has_split_exports()
+    }
+
+    /// Where the package was opened from, if it was opened with [`AssetPackage::open`]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Read the payload of some bulk data, wherever it's stored. Payloads in separate files can only be read if the package was
+    /// opened with [`AssetPackage::open`].
+    pub fn bulk_data_reader(&mut self, header: &BulkDataHeader) -> Result<Box<dyn Read + '_>> {
+        match header.location() {
+            BulkDataLocation::SeparateFile(file) => {
+                let path = self
+                    .path
+                    .as_ref()
+                    .and_then(|path| header.separate_file_path(path))
+                    .ok_or_else(|| {
+                        Error::InvalidBulkData(format!(
+                            "payload is stored in a separate .{} file, but the package path is unknown",
+                            file.extension()
+                        ))
+                    })?;
+                let file = BufReader::new(File::open(path)?);
+                Ok(Box::new(header.reader(file)?))
+            }
+            BulkDataLocation::Inline | BulkDataLocation::EndOfFile => {
+                Ok(Box::new(self.header.bulk_data_reader(header)?))
+            }
+        }
+    }
+
+    pub fn into_header(self) -> AssetHeader<PackageReader<R>> {
+        self.header
+    }
+}
+
+impl<R> Deref for AssetPackage<R> {
+    type Target = AssetHeader<PackageReader<R>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.header
+    }
+}
+
+impl<R> DerefMut for AssetPackage<R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.header
+    }
+}
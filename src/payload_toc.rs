@@ -0,0 +1,194 @@
+use crate::{Error, Result};
+use binread::BinReaderExt;
+use std::{
+    fmt,
+    io::{Read, Seek},
+};
+
+/// Tag that starts the payload table of contents (C++ name: `FPackageTrailer::FHeader::HeaderTag`)
+const HEADER_TAG: u64 = 0xD1C43B2E80A5F697;
+/// Versions of the payload table of contents (C++ name: `EPackageTrailerVersion`)
+const PACKAGE_TRAILER_VERSION_ACCESS_PER_PAYLOAD: u32 = 1;
+const PACKAGE_TRAILER_VERSION_PAYLOAD_FLAGS: u32 = 2;
+
+/// A 160-bit BLAKE3 hash, which identifies a payload by its contents (C++ name: `FIoHash`)
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct IoHash(pub [u8; 20]);
+
+impl fmt::Display for IoHash {
+    /// Formats the hash as lowercase hex, like `LexToString(FIoHash)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where the data of a payload can be found (C++ name: `EPayloadAccessMode`)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PayloadAccessMode {
+    /// The payload is stored in the package, after the table of contents
+    Local,
+    /// The payload is stored in the table of contents of another package
+    Referenced,
+    /// The payload has been pushed to a virtualization backend (e.g. a shared DDC or source control), and isn't in the package
+    Virtualized,
+}
+
+impl TryFrom<u8> for PayloadAccessMode {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Ok(match value {
+            0 => Self::Local,
+            1 => Self::Referenced,
+            2 => Self::Virtualized,
+            _ => {
+                return Err(Error::InvalidPayloadToc(format!(
+                    "invalid payload access mode {}",
+                    value
+                )));
+            }
+        })
+    }
+}
+
+/// An entry in the [`PayloadToc`], describing a single editor bulk data payload (C++ name: `FLookupTableEntry`)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PayloadTocEntry {
+    /// Hash of the payload (C++ name: `Identifier`)
+    pub identifier: IoHash,
+    /// Where the payload starts in the package, which is only known for [`PayloadAccessMode::Local`] payloads
+    /// (C++ name: `OffsetInFile`)
+    pub offset_in_file: Option<u64>,
+    /// Size of the payload as it's stored (C++ name: `CompressedSize`)
+    pub compressed_size: u64,
+    /// Size of the payload after decompressing it (C++ name: `RawSize`)
+    pub raw_size: u64,
+    /// The `EPayloadFlags` of the payload, only serialized by newer versions of the table (C++ name: `Flags`)
+    pub flags: u16,
+    /// The `EPayloadFilterReason` flags, i.e. why the payload wasn't virtualized (C++ name: `FilterFlags`)
+    pub filter_flags: u16,
+    /// (C++ name: `AccessMode`)
+    pub access_mode: PayloadAccessMode,
+}
+
+impl PayloadTocEntry {
+    /// True if the payload is stored in the package itself
+    pub fn is_local(&self) -> bool {
+        self.access_mode == PayloadAccessMode::Local
+    }
+
+    /// True if the payload has been moved out of the package into a virtualization backend
+    pub fn is_virtualized(&self) -> bool {
+        self.access_mode == PayloadAccessMode::Virtualized
+    }
+}
+
+/// The table of contents for the editor bulk data payloads of a package, stored behind
+/// [`crate::AssetHeader::payload_toc_offset`]. This is the header of the package trailer, which is followed by the data of the
+/// local payloads. (See `FPackageTrailer::TryLoad`)
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PayloadToc {
+    /// Version of the table format (C++ name: `EPackageTrailerVersion`)
+    pub version: u32,
+    /// Size of the data of the local payloads that follows the table (C++ name: `PayloadsDataLength`)
+    pub payloads_data_length: u64,
+    /// The payloads in the package (C++ name: `PayloadLookupTable`)
+    pub entries: Vec<PayloadTocEntry>,
+}
+
+impl PayloadToc {
+    /// Read the table at the current position of `reader`
+    pub(crate) fn parse<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let toc_offset = reader.stream_position()?;
+        let tag: u64 = reader.read_le()?;
+        if tag != HEADER_TAG {
+            return Err(Error::InvalidPayloadToc(format!(
+                "unexpected tag {:#x}",
+                tag
+            )));
+        }
+
+        let version: u32 = reader.read_le()?;
+        if version > PACKAGE_TRAILER_VERSION_PAYLOAD_FLAGS {
+            return Err(Error::InvalidPayloadToc(format!(
+                "unsupported version {}",
+                version
+            )));
+        }
+        let _header_length: u32 = reader.read_le()?;
+        let payloads_data_length = reader.read_le()?;
+
+        // The access mode used to be shared by all the payloads
+        let legacy_access_mode = if version < PACKAGE_TRAILER_VERSION_ACCESS_PER_PAYLOAD {
+            Some(PayloadAccessMode::try_from(reader.read_le::<u8>()?)?)
+        } else {
+            None
+        };
+
+        let count: i32 = reader.read_le()?;
+        let count = usize::try_from(count).map_err(|_error| {
+            Error::InvalidPayloadToc(format!("invalid payload count {}", count))
+        })?;
+        let mut entries = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            let identifier = IoHash(reader.read_le()?);
+            let offset_in_file: i64 = reader.read_le()?;
+            let compressed_size = reader.read_le()?;
+            let raw_size = reader.read_le()?;
+            let (flags, filter_flags) = if version >= PACKAGE_TRAILER_VERSION_PAYLOAD_FLAGS {
+                (reader.read_le()?, reader.read_le()?)
+            } else {
+                (0, 0)
+            };
+            let access_mode = match legacy_access_mode {
+                Some(access_mode) => access_mode,
+                None => PayloadAccessMode::try_from(reader.read_le::<u8>()?)?,
+            };
+
+            // Local payloads are stored relative to the start of the table
+            let offset_in_file = if access_mode == PayloadAccessMode::Local {
+                Some(
+                    toc_offset
+                        .checked_add_signed(offset_in_file)
+                        .ok_or_else(|| {
+                            Error::InvalidPayloadToc(format!(
+                                "invalid payload offset {}",
+                                offset_in_file
+                            ))
+                        })?,
+                )
+            } else {
+                None
+            };
+
+            entries.push(PayloadTocEntry {
+                identifier,
+                offset_in_file,
+                compressed_size,
+                raw_size,
+                flags,
+                filter_flags,
+                access_mode,
+            });
+        }
+
+        Ok(Self {
+            version,
+            payloads_data_length,
+            entries,
+        })
+    }
+
+    /// The payloads that are stored in the package itself
+    pub fn local_payloads(&self) -> impl Iterator<Item = &PayloadTocEntry> {
+        self.entries.iter().filter(|entry| entry.is_local())
+    }
+
+    /// The payloads that have been moved into a virtualization backend
+    pub fn virtualized_payloads(&self) -> impl Iterator<Item = &PayloadTocEntry> {
+        self.entries.iter().filter(|entry| entry.is_virtualized())
+    }
+}
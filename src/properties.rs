@@ -0,0 +1,1035 @@
+use crate::{
+    Error, Guid, KnownCustomVersion, ObjectReference, ObjectVersion, ObjectVersionUE5, Result,
+    SoftObjectPath,
+    archive::{Archive, SerializedCustomVersion, SerializedObjectVersion},
+    resolve_name, resolve_soft_object_path,
+    serialization::{Parseable, UnrealNameReference, UnrealSoftObjectPath, UnrealString},
+    unversioned::{PropertySchemaProvider, UnversionedHeader, flatten_schema},
+};
+use binread::BinReaderExt;
+use bitflags::bitflags;
+use std::{
+    fmt,
+    io::{Read, Seek, SeekFrom},
+    str::FromStr,
+};
+
+/// `FEditorObjectVersion::CultureInvariantTextSerializationKeyStability`, which added culture invariant strings to `FTextHistory_None`
+const EDITOR_OBJECT_VERSION_CULTURE_INVARIANT_TEXT: i32 = 32;
+
+bitflags! {
+    /// Flags describing which optional fields follow a property tag (C++ name: `EPropertyTagFlags`)
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    struct PropertyTagFlags: u8 {
+        const HasArrayIndex = 0x01;
+        const HasPropertyGuid = 0x02;
+        const HasPropertyExtensions = 0x04;
+        const HasBinaryOrNativeSerialize = 0x08;
+        const BoolTrue = 0x10;
+        const SkippedSerialize = 0x20;
+    }
+}
+
+bitflags! {
+    /// Extra data serialized with a property tag (C++ name: `EPropertyTagExtension`)
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    struct PropertyTagExtensions: u8 {
+        const ReserveForFutureUse = 0x01;
+        const OverridableInformation = 0x02;
+    }
+}
+
+bitflags! {
+    /// Extra data serialized before the tagged properties of an object (C++ name: `EClassSerializationControlExtension`)
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    struct ClassSerializationControlExtensions: u8 {
+        const ReserveForFutureUse = 0x01;
+        const OverridableSerializationInformation = 0x02;
+    }
+}
+
+/// The header written before each tagged property, describing its name & type (C++ name: `FPropertyTag`)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertyTag {
+    /// Name of the property (C++ name: `Name`)
+    pub name: String,
+    /// Type of the property, e.g. `IntProperty` (C++ name: `Type`)
+    pub type_name: String,
+    /// Number of bytes used by the value of the property (C++ name: `Size`)
+    pub size: i32,
+    /// Index of the element if the property is a static array, i.e. `int32 Values[4]` in C++ (C++ name: `ArrayIndex`)
+    pub array_index: i32,
+    /// Name of the struct for `StructProperty` (C++ name: `StructName`)
+    pub struct_name: Option<String>,
+    /// Guid of the struct for `StructProperty`, used for user defined structs (C++ name: `StructGuid`)
+    pub struct_guid: Option<Guid>,
+    /// Name of the enum for `ByteProperty` and `EnumProperty`, if the property is an enum (C++ name: `EnumName`)
+    pub enum_name: Option<String>,
+    /// Type of the elements for `ArrayProperty` and `SetProperty`, and of the keys for `MapProperty` (C++ name: `InnerType`)
+    pub inner_type: Option<String>,
+    /// Type of the values for `MapProperty` (C++ name: `ValueType`)
+    pub value_type: Option<String>,
+    /// Value of a `BoolProperty`, which is stored in the tag rather than after it (C++ name: `BoolVal`)
+    pub bool_value: bool,
+    /// Guid of the property, used for properties in Blueprints (C++ name: `PropertyGuid`)
+    pub property_guid: Option<Guid>,
+    /// The complete type of the property, including the types of any struct, enum or container elements. Only serialized since
+    /// [`ObjectVersionUE5::PROPERTY_TAG_COMPLETE_TYPE_NAME`] (C++ name: `TypeName`)
+    pub complete_type_name: Option<PropertyTypeName>,
+    /// True if the value uses the binary or native serialization of its type instead of tagged properties (C++ name:
+    /// `bHasBinaryOrNativeSerialize`)
+    pub binary_or_native_serialize: bool,
+    /// How the property overrides the value from its archetype, if the tag has overridable serialization information
+    /// (C++ name: `OverridableOperation`)
+    pub overridable_operation: Option<OverriddenPropertyOperation>,
+    /// Whether the experimental overridable logic was enabled when the property was saved (C++ name:
+    /// `bExperimentalOverridableLogic`)
+    pub experimental_overridable_logic: bool,
+}
+
+impl PropertyTag {
+    /// Tag for a property that we know the layout of, but that isn't serialized with a tag (e.g. fields of an `FVector`)
+    fn untagged(name: &str, type_name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Tag describing the elements of a container, where only the type is known
+    fn element(type_name: &str) -> Self {
+        Self {
+            type_name: type_name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Tag describing the elements of a container with a complete type name, which knows as much about the type as a serialized tag
+    fn element_with_complete_type(complete_type_name: &PropertyTypeName) -> Self {
+        let mut tag = Self::default();
+        tag.set_complete_type_name(complete_type_name.clone());
+        tag
+    }
+
+    /// Set the complete type name, filling in the fields that older versions serialized separately
+    fn set_complete_type_name(&mut self, complete_type_name: PropertyTypeName) {
+        let parameter_name = |index: usize| {
+            complete_type_name
+                .parameters
+                .get(index)
+                .map(|parameter| parameter.name.clone())
+        };
+
+        self.type_name = complete_type_name.name.clone();
+        match self.type_name.as_str() {
+            "StructProperty" => {
+                self.struct_name = parameter_name(0);
+                self.struct_guid = parameter_name(1).and_then(|guid| parse_guid(&guid));
+            }
+            "ByteProperty" | "EnumProperty" => self.enum_name = parameter_name(0),
+            "ArrayProperty" | "SetProperty" => self.inner_type = parameter_name(0),
+            "MapProperty" => {
+                self.inner_type = parameter_name(0);
+                self.value_type = parameter_name(1);
+            }
+            _ => {}
+        }
+        self.complete_type_name = Some(complete_type_name);
+    }
+
+    /// Tag describing the element at `parameter_index` of a container (0 for the elements of an array or set, or the keys of a
+    /// map, and 1 for the values of a map)
+    fn container_element(&self, parameter_index: usize, type_name: &str) -> Self {
+        match self
+            .complete_type_name
+            .as_ref()
+            .and_then(|complete_type_name| complete_type_name.parameters.get(parameter_index))
+        {
+            Some(parameter) => Self::element_with_complete_type(parameter),
+            None => Self::element(type_name),
+        }
+    }
+}
+
+/// Parse a guid in the format `FGuid::ToString` uses by default, which is how guids are stored in a [`PropertyTypeName`]
+fn parse_guid(guid: &str) -> Option<Guid> {
+    if guid.len() != 32 || !guid.is_ascii() {
+        return None;
+    }
+    let component = |index: usize| u32::from_str_radix(&guid[index * 8..(index + 1) * 8], 16).ok();
+    Some(Guid::new(
+        component(0)?,
+        component(1)?,
+        component(2)?,
+        component(3)?,
+    ))
+}
+
+/// The complete type of a property, as a tree of names. For example an array of vectors is
+/// `ArrayProperty(StructProperty(Vector(/Script/CoreUObject)))`, where the struct is named by its object name with its package as a
+/// parameter. (C++ name: `FPropertyTypeName`)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertyTypeName {
+    pub name: String,
+    pub parameters: Vec<PropertyTypeName>,
+}
+
+impl fmt::Display for PropertyTypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.parameters.is_empty() {
+            write!(f, "(")?;
+            for (index, parameter) in self.parameters.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{}", parameter)?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PropertyTypeName {
+    type Err = Error;
+
+    /// Parse a type name in the format it's displayed in, e.g. `MapProperty(NameProperty,StructProperty(Vector(/Script/CoreUObject)))`
+    fn from_str(type_name: &str) -> Result<Self> {
+        // Returns the parsed type name and the remaining input
+        fn parse(input: &str) -> Option<(PropertyTypeName, &str)> {
+            let name_end = input.find(['(', ',', ')']).unwrap_or(input.len());
+            let name = input[..name_end].trim();
+            if name.is_empty() {
+                return None;
+            }
+
+            let mut parsed = PropertyTypeName {
+                name: name.to_string(),
+                parameters: Vec::new(),
+            };
+            let mut rest = &input[name_end..];
+            if let Some(parameters) = rest.strip_prefix('(') {
+                rest = parameters;
+                loop {
+                    let (parameter, after_parameter) = parse(rest)?;
+                    parsed.parameters.push(parameter);
+                    if let Some(next_parameter) = after_parameter.strip_prefix(',') {
+                        rest = next_parameter;
+                    } else {
+                        rest = after_parameter.trim_start().strip_prefix(')')?;
+                        break;
+                    }
+                }
+            }
+            Some((parsed, rest.trim_start()))
+        }
+
+        match parse(type_name) {
+            Some((parsed, "")) => Ok(parsed),
+            _ => Err(Error::InvalidPropertyData(format!(
+                "invalid property type name {:?}",
+                type_name
+            ))),
+        }
+    }
+}
+
+/// How an overridable property changes the value it inherits from its archetype (C++ name: `EOverriddenPropertyOperation`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverriddenPropertyOperation {
+    /// The property isn't overridden
+    None,
+    /// Some of the sub properties are overridden
+    Modified,
+    /// The whole property is replaced
+    Replaced,
+    /// An element was added to a container
+    Add,
+    /// An element was removed from a container
+    Remove,
+}
+
+impl TryFrom<u8> for OverriddenPropertyOperation {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Ok(match value {
+            0 => Self::None,
+            1 => Self::Modified,
+            2 => Self::Replaced,
+            3 => Self::Add,
+            4 => Self::Remove,
+            _ => {
+                return Err(Error::InvalidPropertyData(format!(
+                    "invalid overridden property operation {}",
+                    value
+                )));
+            }
+        })
+    }
+}
+
+/// A localizable piece of text (C++ name: `FText`). Only the simpler kinds of text history are supported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Text {
+    /// Flags for the text (C++ name: `ETextFlag`)
+    pub flags: u32,
+    /// The namespace the text should be localized in, `None` for culture invariant text
+    pub namespace: Option<String>,
+    /// The key used to look up the localized text, `None` for culture invariant text
+    pub key: Option<String>,
+    /// The source (or culture invariant) string for the text, if there is one
+    pub source_string: Option<String>,
+}
+
+/// The value of a property. Native structs we know the layout of (like `Vector`) are represented as a [`PropertyValue::Struct`]
+/// with one property per field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    Int8(i8),
+    Int16(i16),
+    Int(i32),
+    Int64(i64),
+    Byte(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Float(f32),
+    Double(f64),
+    Name(String),
+    String(String),
+    Text(Text),
+    /// The fully qualified name of an enum value, e.g. `EMyEnum::Value`
+    Enum(String),
+    /// Reference to an object, used by `ObjectProperty`, `ClassProperty`, `WeakObjectProperty` and `InterfaceProperty`
+    Object(ObjectReference),
+    /// Path to an object, used by `SoftObjectProperty` and `SoftClassProperty`
+    SoftObject(SoftObjectPath),
+    Struct {
+        /// Name of the struct, which is empty if the struct type wasn't serialized (e.g. for the keys of a map)
+        struct_name: String,
+        properties: Vec<Property>,
+    },
+    Array(Vec<PropertyValue>),
+    Set(Vec<PropertyValue>),
+    Map(Vec<(PropertyValue, PropertyValue)>),
+    /// The raw bytes for a value of a type we don't know how to read
+    Unknown(Vec<u8>),
+    /// The raw bytes for a value that failed to read, and why it failed
+    Invalid { bytes: Vec<u8>, error: String },
+    /// A property that is marked as zero in the header of unversioned properties, so it has no serialized data and is the zero
+    /// (default constructed) value of its type
+    Zero,
+}
+
+/// A property and its value, read from the serialized data of an export
+#[derive(Clone, Debug, PartialEq)]
+pub struct Property {
+    pub tag: PropertyTag,
+    pub value: PropertyValue,
+}
+
+impl Property {
+    /// Name of the property
+    pub fn name(&self) -> &str {
+        &self.tag.name
+    }
+}
+
+/// Where a value is being read from, which determines how some types are serialized
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ValueContext {
+    /// A property with a tag, where the tag describes the type and size of the value
+    Tagged,
+    /// An element of an array, set or map, where only the type of the value is known
+    Element,
+}
+
+/// Reads tagged properties (see `UStruct::SerializeTaggedProperties`) from an archive
+pub(crate) struct PropertyReader<'a, R> {
+    archive: &'a mut Archive<R>,
+    names: &'a [String],
+    soft_object_paths: &'a [SoftObjectPath],
+    /// Schemas used to read unversioned properties, which are expected instead of tagged properties if this is set
+    schemas: Option<&'a dyn PropertySchemaProvider>,
+}
+
+impl<'a, R> PropertyReader<'a, R>
+where
+    R: Seek + Read,
+{
+    pub fn new(
+        archive: &'a mut Archive<R>,
+        names: &'a [String],
+        soft_object_paths: &'a [SoftObjectPath],
+    ) -> Self {
+        Self {
+            archive,
+            names,
+            soft_object_paths,
+            schemas: None,
+        }
+    }
+
+    /// Read unversioned properties using `schemas` instead of tagged properties
+    pub fn with_schemas(mut self, schemas: &'a dyn PropertySchemaProvider) -> Self {
+        self.schemas = Some(schemas);
+        self
+    }
+
+    fn read_name(&mut self) -> Result<String> {
+        let name_reference = UnrealNameReference::parse_inline(self.archive)?;
+        Ok(resolve_name(self.names, &name_reference)?.into_owned())
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        UnrealString::parse_inline(self.archive)
+    }
+
+    fn read_guid(&mut self) -> Result<Guid> {
+        Ok(self.archive.read_le()?)
+    }
+
+    /// Read a `bool` the way `FArchive` serializes it, as a 32-bit integer
+    fn read_bool(&mut self) -> Result<bool> {
+        Ok(self.archive.read_le::<u32>()? != 0)
+    }
+
+    fn read_bytes(&mut self, count: u64) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        (&mut self.archive).take(count).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != count {
+            return Err(Error::InvalidPropertyData(format!(
+                "expected {} bytes of data, found {}",
+                count,
+                bytes.len()
+            )));
+        }
+        Ok(bytes)
+    }
+
+    /// Read a single tag, returns `None` when reaching the `None` tag that terminates a list of tagged properties
+    fn read_tag(&mut self) -> Result<Option<PropertyTag>> {
+        let name = self.read_name()?;
+        if name == "None" {
+            return Ok(None);
+        }
+
+        if self
+            .archive
+            .serialized_with(ObjectVersionUE5::PROPERTY_TAG_COMPLETE_TYPE_NAME)
+        {
+            return self.read_tag_with_complete_type_name(name).map(Some);
+        }
+
+        let type_name = self.read_name()?;
+        let size = self.archive.read_le()?;
+        let array_index = self.archive.read_le()?;
+        let mut tag = PropertyTag {
+            name,
+            type_name,
+            size,
+            array_index,
+            ..Default::default()
+        };
+
+        match tag.type_name.as_str() {
+            "StructProperty" => {
+                tag.struct_name = Some(self.read_name()?);
+                if self
+                    .archive
+                    .serialized_with(ObjectVersion::VER_UE4_STRUCT_GUID_IN_PROPERTY_TAG)
+                {
+                    tag.struct_guid = Some(self.read_guid()?);
+                }
+            }
+            "BoolProperty" => tag.bool_value = self.archive.read_le::<u8>()? != 0,
+            "ByteProperty" | "EnumProperty" => {
+                let enum_name = self.read_name()?;
+                tag.enum_name = (enum_name != "None").then_some(enum_name);
+            }
+            "ArrayProperty"
+                if self
+                    .archive
+                    .serialized_with(ObjectVersion::VAR_UE4_ARRAY_PROPERTY_INNER_TAGS) =>
+            {
+                tag.inner_type = Some(self.read_name()?);
+            }
+            "SetProperty"
+                if self
+                    .archive
+                    .serialized_with(ObjectVersion::VER_UE4_PROPERTY_TAG_SET_MAP_SUPPORT) =>
+            {
+                tag.inner_type = Some(self.read_name()?);
+            }
+            "MapProperty"
+                if self
+                    .archive
+                    .serialized_with(ObjectVersion::VER_UE4_PROPERTY_TAG_SET_MAP_SUPPORT) =>
+            {
+                tag.inner_type = Some(self.read_name()?);
+                tag.value_type = Some(self.read_name()?);
+            }
+            _ => {}
+        }
+
+        if self
+            .archive
+            .serialized_with(ObjectVersion::VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG)
+            && self.archive.read_le::<u8>()? != 0
+        {
+            tag.property_guid = Some(self.read_guid()?);
+        }
+
+        if self
+            .archive
+            .serialized_with(ObjectVersionUE5::PROPERTY_TAG_EXTENSION_AND_OVERRIDABLE_SERIALIZATION)
+        {
+            self.read_tag_extensions(&mut tag)?;
+        }
+
+        Ok(Some(tag))
+    }
+
+    /// Read the rest of a tag in the layout used since [`ObjectVersionUE5::PROPERTY_TAG_COMPLETE_TYPE_NAME`], where the type is a
+    /// [`PropertyTypeName`] and the optional fields are described by [`PropertyTagFlags`]
+    fn read_tag_with_complete_type_name(&mut self, name: String) -> Result<PropertyTag> {
+        let complete_type_name = self.read_type_name()?;
+        let size = self.archive.read_le()?;
+        let flags = PropertyTagFlags::from_bits_truncate(self.archive.read_le()?);
+
+        let mut tag = PropertyTag {
+            name,
+            size,
+            bool_value: flags.contains(PropertyTagFlags::BoolTrue),
+            binary_or_native_serialize: flags
+                .contains(PropertyTagFlags::HasBinaryOrNativeSerialize),
+            ..Default::default()
+        };
+        tag.set_complete_type_name(complete_type_name);
+
+        if flags.contains(PropertyTagFlags::HasArrayIndex) {
+            tag.array_index = self.archive.read_le()?;
+        }
+        if flags.contains(PropertyTagFlags::HasPropertyGuid) {
+            tag.property_guid = Some(self.read_guid()?);
+        }
+        if flags.contains(PropertyTagFlags::HasPropertyExtensions) {
+            self.read_tag_extensions(&mut tag)?;
+        }
+
+        Ok(tag)
+    }
+
+    /// Read a [`PropertyTypeName`], which is serialized as a pre-order list of nodes, each with the number of parameters it has
+    fn read_type_name(&mut self) -> Result<PropertyTypeName> {
+        let mut nodes = Vec::new();
+        let mut remaining_nodes = 1;
+        while remaining_nodes > 0 {
+            let name = self.read_name()?;
+            let parameter_count = self.read_element_count()?;
+            nodes.push((name, parameter_count));
+            remaining_nodes = remaining_nodes - 1 + parameter_count as usize;
+
+            // Each node is at least 12 bytes, so this stops us from reading forever with corrupt data
+            if remaining_nodes > 1024 {
+                return Err(Error::InvalidPropertyData(
+                    "property type name has too many parameters".to_string(),
+                ));
+            }
+        }
+
+        fn build(nodes: &mut std::vec::IntoIter<(String, u32)>) -> PropertyTypeName {
+            let (name, parameter_count) = nodes.next().unwrap_or_default();
+            PropertyTypeName {
+                name,
+                parameters: (0..parameter_count).map(|_| build(nodes)).collect(),
+            }
+        }
+        Ok(build(&mut nodes.into_iter()))
+    }
+
+    /// Read the extensions of a tag (see `FPropertyTag::SerializePropertyExtensions`)
+    fn read_tag_extensions(&mut self, tag: &mut PropertyTag) -> Result<()> {
+        let extensions = PropertyTagExtensions::from_bits_truncate(self.archive.read_le()?);
+        if extensions.contains(PropertyTagExtensions::OverridableInformation) {
+            tag.overridable_operation = Some(self.archive.read_le::<u8>()?.try_into()?);
+            tag.experimental_overridable_logic = self.read_bool()?;
+        }
+        Ok(())
+    }
+
+    /// Read the properties of an object of the class `class_name`, which are preceded by some object level data in newer versions
+    /// (see `UObject::SerializeScriptProperties`)
+    pub fn read_object_properties(
+        &mut self,
+        class_name: &str,
+        end_offset: u64,
+    ) -> Result<Vec<Property>> {
+        if self
+            .archive
+            .serialized_with(ObjectVersionUE5::PROPERTY_TAG_EXTENSION_AND_OVERRIDABLE_SERIALIZATION)
+        {
+            let extensions =
+                ClassSerializationControlExtensions::from_bits_truncate(self.archive.read_le()?);
+            if extensions
+                .contains(ClassSerializationControlExtensions::OverridableSerializationInformation)
+            {
+                let _overridden_operation: OverriddenPropertyOperation =
+                    self.archive.read_le::<u8>()?.try_into()?;
+            }
+        }
+
+        if self.schemas.is_some() {
+            self.read_unversioned_properties(class_name, end_offset)
+        } else {
+            self.read_tagged_properties(end_offset)
+        }
+    }
+
+    /// Read the unversioned properties of the struct `struct_name`, which are a header describing which properties of the struct's
+    /// schema are serialized, followed by their values without any tags (see `SerializeUnversionedProperties`). Properties that are
+    /// marked as zero in the header have no data, and are returned as [`PropertyValue::Zero`].
+    fn read_unversioned_properties(
+        &mut self,
+        struct_name: &str,
+        end_offset: u64,
+    ) -> Result<Vec<Property>> {
+        let schemas = self
+            .schemas
+            .ok_or_else(|| Error::MissingSchema(struct_name.to_string()))?;
+        let schema = flatten_schema(schemas, struct_name)?;
+        let header = UnversionedHeader::parse(self.archive)?;
+
+        let mut properties = Vec::with_capacity(header.properties.len());
+        for (schema_index, is_zero) in header.properties {
+            let (property_schema, array_index) = schema.get(schema_index).ok_or_else(|| {
+                Error::InvalidPropertyData(format!(
+                    "{} has no property with index {}",
+                    struct_name, schema_index
+                ))
+            })?;
+
+            let mut tag = PropertyTag::element_with_complete_type(&property_schema.type_name);
+            tag.name = property_schema.name.clone();
+            tag.array_index = *array_index as i32;
+            let value = if is_zero {
+                PropertyValue::Zero
+            } else {
+                self.read_value(&tag, ValueContext::Element, end_offset)?
+            };
+            properties.push(Property { tag, value });
+        }
+
+        Ok(properties)
+    }
+
+    /// Convert the numeric value of an unversioned enum to the name of the value if we know it, or `numeric_value` otherwise
+    fn unversioned_enum_value(
+        &self,
+        tag: &PropertyTag,
+        numeric_value: PropertyValue,
+    ) -> PropertyValue {
+        let value = match numeric_value {
+            PropertyValue::Byte(value) => value as i64,
+            PropertyValue::Int8(value) => value as i64,
+            PropertyValue::Int16(value) => value as i64,
+            PropertyValue::Int(value) => value as i64,
+            PropertyValue::Int64(value) => value,
+            PropertyValue::UInt16(value) => value as i64,
+            PropertyValue::UInt32(value) => value as i64,
+            PropertyValue::UInt64(value) => value as i64,
+            _ => return numeric_value,
+        };
+
+        self.schemas
+            .zip(tag.enum_name.as_deref())
+            .and_then(|(schemas, enum_name)| schemas.enum_value_name(enum_name, value))
+            .map(|value_name| PropertyValue::Enum(value_name.to_string()))
+            .unwrap_or(numeric_value)
+    }
+
+    /// Read tagged properties until the terminating `None` tag, or until `end_offset` if it's reached first
+    pub fn read_tagged_properties(&mut self, end_offset: u64) -> Result<Vec<Property>> {
+        let mut properties = Vec::new();
+        while self.archive.stream_position()? < end_offset {
+            let Some(tag) = self.read_tag()? else {
+                break;
+            };
+
+            let value_offset = self.archive.stream_position()?;
+            let value_end_offset = value_offset
+                .checked_add_signed(tag.size as i64)
+                .filter(|offset| tag.size >= 0 && *offset <= end_offset)
+                .ok_or_else(|| {
+                    Error::InvalidPropertyData(format!(
+                        "property {} has invalid size {}",
+                        tag.name, tag.size
+                    ))
+                })?;
+
+            // If we fail to read a value (or read the wrong amount of data), we can still recover using the size from the tag
+            let value = match self.read_value(&tag, ValueContext::Tagged, value_end_offset) {
+                Ok(value) if self.archive.stream_position()? == value_end_offset => value,
+                result => {
+                    let error = match result {
+                        Ok(_) => format!(
+                            "value of {} used {} bytes, but its tag has {} bytes",
+                            tag.name,
+                            self.archive.stream_position()?.saturating_sub(value_offset),
+                            tag.size
+                        ),
+                        Err(error) => error.to_string(),
+                    };
+                    self.archive.seek(SeekFrom::Start(value_offset))?;
+                    PropertyValue::Invalid {
+                        bytes: self.read_bytes(tag.size as u64)?,
+                        error,
+                    }
+                }
+            };
+
+            properties.push(Property { tag, value });
+        }
+
+        Ok(properties)
+    }
+
+    /// Read a single value of the type described by `tag`, the value can't extend past `end_offset`
+    fn read_value(
+        &mut self,
+        tag: &PropertyTag,
+        context: ValueContext,
+        end_offset: u64,
+    ) -> Result<PropertyValue> {
+        Ok(match tag.type_name.as_str() {
+            "BoolProperty" => match context {
+                ValueContext::Tagged => PropertyValue::Bool(tag.bool_value),
+                ValueContext::Element => PropertyValue::Bool(self.archive.read_le::<u8>()? != 0),
+            },
+            "Int8Property" => PropertyValue::Int8(self.archive.read_le()?),
+            "Int16Property" => PropertyValue::Int16(self.archive.read_le()?),
+            "IntProperty" => PropertyValue::Int(self.archive.read_le()?),
+            "Int64Property" => PropertyValue::Int64(self.archive.read_le()?),
+            "UInt16Property" => PropertyValue::UInt16(self.archive.read_le()?),
+            "UInt32Property" => PropertyValue::UInt32(self.archive.read_le()?),
+            "UInt64Property" => PropertyValue::UInt64(self.archive.read_le()?),
+            "FloatProperty" => PropertyValue::Float(self.archive.read_le()?),
+            "DoubleProperty" => PropertyValue::Double(self.archive.read_le()?),
+            "ByteProperty" => {
+                // Bytes that represent an enum are serialized as the name of the enum value
+                let is_enum = match context {
+                    ValueContext::Tagged => tag.enum_name.is_some(),
+                    ValueContext::Element if tag.complete_type_name.is_some() => {
+                        tag.enum_name.is_some()
+                    }
+                    ValueContext::Element => tag.size > 1,
+                };
+                if self.schemas.is_some() {
+                    // Unversioned properties store enums as their numeric value
+                    let value = PropertyValue::Byte(self.archive.read_le()?);
+                    self.unversioned_enum_value(tag, value)
+                } else if is_enum {
+                    PropertyValue::Enum(self.read_name()?)
+                } else {
+                    PropertyValue::Byte(self.archive.read_le()?)
+                }
+            }
+            "EnumProperty" if self.schemas.is_some() => {
+                let underlying_type = tag
+                    .complete_type_name
+                    .as_ref()
+                    .and_then(|complete_type_name| complete_type_name.parameters.get(1))
+                    .map_or("ByteProperty", |underlying_type| {
+                        underlying_type.name.as_str()
+                    });
+                let value = self.read_value(
+                    &PropertyTag::element(underlying_type),
+                    ValueContext::Element,
+                    end_offset,
+                )?;
+                self.unversioned_enum_value(tag, value)
+            }
+            "EnumProperty" => PropertyValue::Enum(self.read_name()?),
+            "NameProperty" => PropertyValue::Name(self.read_name()?),
+            "StrProperty" => PropertyValue::String(self.read_string()?),
+            "TextProperty" => PropertyValue::Text(self.read_text()?),
+            "ObjectProperty" | "ClassProperty" | "WeakObjectProperty" | "InterfaceProperty" => {
+                PropertyValue::Object(ObjectReference::from(self.archive.read_le::<i32>()?))
+            }
+            "SoftObjectProperty"
+            | "SoftClassProperty"
+            | "AssetObjectProperty"
+            | "AssetClassProperty" => PropertyValue::SoftObject(self.read_soft_object_path()?),
+            "StructProperty" => self.read_struct(tag.struct_name.as_deref(), end_offset)?,
+            "ArrayProperty" => PropertyValue::Array(self.read_elements(tag, end_offset)?),
+            "SetProperty" => self.read_set(tag, end_offset)?,
+            "MapProperty" => self.read_map(tag, end_offset)?,
+            _ => match context {
+                ValueContext::Tagged => {
+                    PropertyValue::Unknown(self.read_bytes(tag.size.max(0) as u64)?)
+                }
+                ValueContext::Element => {
+                    return Err(Error::InvalidPropertyData(format!(
+                        "can't determine the size of {} elements",
+                        tag.type_name
+                    )));
+                }
+            },
+        })
+    }
+
+    fn read_element_count(&mut self) -> Result<u32> {
+        let count: i32 = self.archive.read_le()?;
+        if count < 0 {
+            return Err(Error::InvalidPropertyData(format!(
+                "invalid element count {}",
+                count
+            )));
+        }
+        Ok(count as u32)
+    }
+
+    /// Read the elements of an `ArrayProperty` or `SetProperty` described by `tag` (see `FArrayProperty::SerializeItem`)
+    fn read_elements(&mut self, tag: &PropertyTag, end_offset: u64) -> Result<Vec<PropertyValue>> {
+        let inner_type = tag
+            .inner_type
+            .as_deref()
+            .ok_or_else(|| Error::InvalidPropertyData(format!("{} has no inner type", tag.name)))?;
+
+        let count = self.read_element_count()?;
+        let mut element_tag = tag.container_element(0, inner_type);
+        if element_tag.complete_type_name.is_some() {
+            // The complete type name tells us everything the inner tag did in older versions, so the inner tag is no longer serialized
+        } else if inner_type == "StructProperty"
+            && tag.type_name == "ArrayProperty"
+            && self
+                .archive
+                .serialized_with(ObjectVersion::VER_UE4_INNER_ARRAY_TAG_INFO)
+        {
+            // Arrays of structs have a full tag for their elements, so we know which struct they contain
+            if let Some(inner_tag) = self.read_tag()? {
+                element_tag = inner_tag;
+            }
+        } else if count > 0 && tag.size > 0 {
+            // There's no way to tell whether a `ByteProperty` is an enum, so we guess based on the size of each element
+            let element_data_size = end_offset.saturating_sub(self.archive.stream_position()?);
+            element_tag.size = (element_data_size / count as u64) as i32;
+        }
+
+        let mut elements = Vec::with_capacity(count.min(1024) as usize);
+        for _ in 0..count {
+            elements.push(self.read_value(&element_tag, ValueContext::Element, end_offset)?);
+        }
+        Ok(elements)
+    }
+
+    /// Read the elements of a `SetProperty` described by `tag` (see `FSetProperty::SerializeItem`)
+    fn read_set(&mut self, tag: &PropertyTag, end_offset: u64) -> Result<PropertyValue> {
+        let inner_type = tag
+            .inner_type
+            .as_deref()
+            .ok_or_else(|| Error::InvalidPropertyData(format!("{} has no inner type", tag.name)))?;
+        let element_tag = tag.container_element(0, inner_type);
+
+        let num_elements_to_remove = self.read_element_count()?;
+        for _ in 0..num_elements_to_remove {
+            self.read_value(&element_tag, ValueContext::Element, end_offset)?;
+        }
+
+        Ok(PropertyValue::Set(self.read_elements(tag, end_offset)?))
+    }
+
+    /// Read the entries of a `MapProperty` described by `tag` (see `FMapProperty::SerializeItem`)
+    fn read_map(&mut self, tag: &PropertyTag, end_offset: u64) -> Result<PropertyValue> {
+        let (Some(key_type), Some(value_type)) = (&tag.inner_type, &tag.value_type) else {
+            return Err(Error::InvalidPropertyData(format!(
+                "{} has no key or value type",
+                tag.name
+            )));
+        };
+        let key_tag = tag.container_element(0, key_type);
+        let value_tag = tag.container_element(1, value_type);
+
+        let num_keys_to_remove = self.read_element_count()?;
+        for _ in 0..num_keys_to_remove {
+            self.read_value(&key_tag, ValueContext::Element, end_offset)?;
+        }
+
+        let count = self.read_element_count()?;
+        let mut entries = Vec::with_capacity(count.min(1024) as usize);
+        for _ in 0..count {
+            let key = self.read_value(&key_tag, ValueContext::Element, end_offset)?;
+            let value = self.read_value(&value_tag, ValueContext::Element, end_offset)?;
+            entries.push((key, value));
+        }
+        Ok(PropertyValue::Map(entries))
+    }
+
+    /// Read an `FSoftObjectPath`, which is an index into the package's soft object path list if it has one (see
+    /// `FLinkerLoad::operator<<(FSoftObjectPath&)`)
+    fn read_soft_object_path(&mut self) -> Result<SoftObjectPath> {
+        if !self.soft_object_paths.is_empty() {
+            let index: i32 = self.archive.read_le()?;
+            return usize::try_from(index)
+                .ok()
+                .and_then(|index| self.soft_object_paths.get(index))
+                .cloned()
+                .ok_or_else(|| {
+                    Error::InvalidPropertyData(format!("invalid soft object path index {}", index))
+                });
+        }
+
+        if self
+            .archive
+            .serialized_with(ObjectVersion::VER_UE4_ADDED_SOFT_OBJECT_PATH)
+        {
+            let serialized_path = UnrealSoftObjectPath::parse_inline(self.archive)?;
+            Ok(resolve_soft_object_path(self.names, &serialized_path)?)
+        } else {
+            // Before `FSoftObjectPath` these were `FStringAssetReference`, which serialized the path as a string
+            let path = self.read_string()?;
+            let (asset_path, sub_path) = path.split_once(':').unwrap_or((&path, ""));
+            let (package_name, asset_name) = asset_path.split_once('.').unwrap_or((asset_path, ""));
+            Ok(SoftObjectPath {
+                package_name: package_name.to_string(),
+                asset_name: asset_name.to_string(),
+                sub_path: sub_path.to_string(),
+            })
+        }
+    }
+
+    /// Read an `FText`, supporting just the `None` and `Base` history types (see `FTextHistory`)
+    fn read_text(&mut self) -> Result<Text> {
+        if self
+            .archive
+            .serialized_without(ObjectVersion::VER_UE4_FTEXT_HISTORY)
+        {
+            return Err(Error::InvalidPropertyData(
+                "text without history is not supported".to_string(),
+            ));
+        }
+
+        let flags = self.archive.read_le()?;
+        let history_type: i8 = self.archive.read_le()?;
+        let mut text = Text {
+            flags,
+            namespace: None,
+            key: None,
+            source_string: None,
+        };
+        match history_type {
+            -1 => {
+                if self.archive.serialized_with_custom_version(
+                    KnownCustomVersion::EditorObjectVersion.guid(),
+                    EDITOR_OBJECT_VERSION_CULTURE_INVARIANT_TEXT,
+                ) && self.read_bool()?
+                {
+                    text.source_string = Some(self.read_string()?);
+                }
+            }
+            0 => {
+                text.namespace = Some(self.read_string()?);
+                text.key = Some(self.read_string()?);
+                text.source_string = Some(self.read_string()?);
+            }
+            _ => {
+                return Err(Error::InvalidPropertyData(format!(
+                    "text history type {} is not supported",
+                    history_type
+                )));
+            }
+        }
+        Ok(text)
+    }
+
+    /// Read a struct, either as one of the native structs we know the binary layout of, or as tagged properties
+    fn read_struct(&mut self, struct_name: Option<&str>, end_offset: u64) -> Result<PropertyValue> {
+        // Large world coordinates changed the core math types to use doubles
+        let real_type = if self
+            .archive
+            .serialized_with(ObjectVersionUE5::LARGE_WORLD_COORDINATES)
+        {
+            "DoubleProperty"
+        } else {
+            "FloatProperty"
+        };
+
+        let fields: &[(&str, &str)] = match struct_name.unwrap_or_default() {
+            "Vector" | "Vector3d" => &[("X", real_type), ("Y", real_type), ("Z", real_type)],
+            "Vector3f" => &[
+                ("X", "FloatProperty"),
+                ("Y", "FloatProperty"),
+                ("Z", "FloatProperty"),
+            ],
+            "Vector2D" => &[("X", real_type), ("Y", real_type)],
+            "Vector4" | "Quat" => &[
+                ("X", real_type),
+                ("Y", real_type),
+                ("Z", real_type),
+                ("W", real_type),
+            ],
+            "Rotator" => &[
+                ("Pitch", real_type),
+                ("Yaw", real_type),
+                ("Roll", real_type),
+            ],
+            "IntPoint" => &[("X", "IntProperty"), ("Y", "IntProperty")],
+            "IntVector" => &[
+                ("X", "IntProperty"),
+                ("Y", "IntProperty"),
+                ("Z", "IntProperty"),
+            ],
+            "Color" => &[
+                ("B", "ByteProperty"),
+                ("G", "ByteProperty"),
+                ("R", "ByteProperty"),
+                ("A", "ByteProperty"),
+            ],
+            "LinearColor" => &[
+                ("R", "FloatProperty"),
+                ("G", "FloatProperty"),
+                ("B", "FloatProperty"),
+                ("A", "FloatProperty"),
+            ],
+            "Guid" => &[
+                ("A", "UInt32Property"),
+                ("B", "UInt32Property"),
+                ("C", "UInt32Property"),
+                ("D", "UInt32Property"),
+            ],
+            "DateTime" | "Timespan" => &[("Ticks", "Int64Property")],
+            "FrameNumber" => &[("Value", "IntProperty")],
+            "SoftObjectPath"
+            | "SoftClassPath"
+            | "StringAssetReference"
+            | "StringClassReference" => &[("Path", "SoftObjectProperty")],
+            _ => &[],
+        };
+
+        let properties = if fields.is_empty() && self.schemas.is_some() {
+            let struct_name = struct_name.ok_or_else(|| {
+                Error::InvalidPropertyData("unversioned struct has no name".to_string())
+            })?;
+            self.read_unversioned_properties(struct_name, end_offset)?
+        } else if fields.is_empty() {
+            self.read_tagged_properties(end_offset)?
+        } else {
+            let mut properties = Vec::with_capacity(fields.len());
+            for (name, type_name) in fields {
+                let tag = PropertyTag::untagged(name, type_name);
+                let value = self.read_value(&tag, ValueContext::Element, end_offset)?;
+                properties.push(Property { tag, value });
+            }
+            properties
+        };
+
+        Ok(PropertyValue::Struct {
+            struct_name: struct_name.unwrap_or_default().to_string(),
+            properties,
+        })
+    }
+}
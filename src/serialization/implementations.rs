@@ -1,6 +1,7 @@
 use binread::{BinRead, BinReaderExt};
 use bit_field::BitField;
 use std::{
+    collections::BTreeMap,
     io::{Read, Seek, SeekFrom},
     marker::PhantomData,
     mem::size_of,
@@ -10,7 +11,7 @@ use std::{
 use crate::{archive::{SerializedFlags, SerializedObjectVersion}, serialization::{
     ArrayStreamInfo, Deferrable, Parseable, ReadInfo, SingleItemStreamInfo, Skippable,
     StreamInfo,
-}, AssetHeader, Error, NameReference, ObjectExport, ObjectImport, ObjectVersion, ObjectVersionUE5, Result, ThumbnailInfo};
+}, AssetHeader, AssetRegistryObject, CompressedChunk, CustomVersion, Error, GenerationInfo, Guid, KnownCustomVersion, NameHashes, NameReference, ObjectExport, ObjectFlags, ObjectImport, ObjectReference, ObjectVersion, ObjectVersionUE5, PackageFlags, Result, ThumbnailFormat, ThumbnailInfo, ObjectThumbnail};
 
 impl<T> Deferrable for T
 where
@@ -169,14 +170,14 @@ impl Skippable for UnrealNameEntryWithHash {
     {
         reader.seek(SeekFrom::Start(stream_info.offset))?;
         skip_string(reader)?;
-        // Seek past two hashes that are no longer used, NonCasePreservingHash and CasePreservingHash
+        // Seek past the two hashes, NonCasePreservingHash and CasePreservingHash
         reader.seek(SeekFrom::Current(size_of::<[u16; 2]>() as i64))?;
         Ok(())
     }
 }
 
 impl Parseable for UnrealNameEntryWithHash {
-    type ParsedType = String;
+    type ParsedType = (String, NameHashes);
 
     fn parse_with_info_seekless<R>(
         reader: &mut R,
@@ -186,8 +187,11 @@ impl Parseable for UnrealNameEntryWithHash {
         R: Seek + Read,
     {
         let string = parse_string(reader)?;
-        let _hash: u32 = reader.read_le()?;
-        Ok(string)
+        let hashes = NameHashes {
+            non_case_preserving: reader.read_le()?,
+            case_preserving: reader.read_le()?,
+        };
+        Ok((string, hashes))
     }
 }
 
@@ -304,96 +308,111 @@ where
     }
 }
 
-/// Size of `FGuid`
-const GUID_SIZE: u64 = 16;
-pub struct UnrealGuid {}
-
-impl Deferrable for UnrealGuid {
-    type StreamInfoType = SingleItemStreamInfo;
-}
-
-impl Skippable for UnrealGuid {
-    fn seek_past_with_info<R>(reader: &mut R, stream_info: &Self::StreamInfoType) -> Result<()>
-    where
-        R: Seek + Read,
-    {
-        reader.seek(SeekFrom::Start(stream_info.offset + GUID_SIZE))?;
-        Ok(())
-    }
-}
-
-/// Size of `FCustomVersion`, when serializing with `ECustomVersionSerializationFormat::Optimized`
-const CUSTOM_VERSION_SIZE: u64 = 20;
+#[derive(Debug)]
 pub struct UnrealCustomVersion {}
 
 impl Deferrable for UnrealCustomVersion {
     type StreamInfoType = SingleItemStreamInfo;
 }
 
-impl Skippable for UnrealCustomVersion {
-    fn seek_past_with_info<R>(reader: &mut R, stream_info: &Self::StreamInfoType) -> Result<()>
+impl Parseable for UnrealCustomVersion {
+    type ParsedType = CustomVersion;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
     where
         R: Seek + Read,
     {
-        reader.seek(SeekFrom::Start(stream_info.offset + CUSTOM_VERSION_SIZE))?;
-        Ok(())
+        let guid = reader.read_le()?;
+        let version = reader.read_le()?;
+        // `ECustomVersionSerializationFormat::Optimized` doesn't serialize the name, so try to look it up
+        let friendly_name =
+            KnownCustomVersion::from_guid(guid).map(|known| known.friendly_name().to_string());
+        Ok(Self::ParsedType {
+            guid,
+            version,
+            friendly_name,
+        })
     }
 }
 
-/// Size of `FGuidCustomVersion_DEPRECATED` excluding the `FString`, when serializing with `ECustomVersionSerializationFormat::Guid`
-const GUID_CUSTOM_VERSION_PREFIX_SIZE: u64 = 20;
+#[derive(Debug)]
 pub struct UnrealGuidCustomVersion {}
 
 impl Deferrable for UnrealGuidCustomVersion {
     type StreamInfoType = SingleItemStreamInfo;
 }
 
-impl Skippable for UnrealGuidCustomVersion {
-    fn seek_past_with_info<R>(reader: &mut R, stream_info: &Self::StreamInfoType) -> Result<()>
+impl Parseable for UnrealGuidCustomVersion {
+    type ParsedType = CustomVersion;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
     where
         R: Seek + Read,
     {
-        reader.seek(SeekFrom::Start(
-            stream_info.offset + GUID_CUSTOM_VERSION_PREFIX_SIZE,
-        ))?;
-        UnrealString::seek_past(reader)?;
-        Ok(())
+        let guid = reader.read_le()?;
+        let version = reader.read_le()?;
+        let friendly_name = parse_string(reader)?;
+        Ok(Self::ParsedType {
+            guid,
+            version,
+            friendly_name: Some(friendly_name),
+        })
     }
 }
 
-/// Size of `FGenerationInfo`
-const GENERATION_INFO_SIZE: u64 = 8;
+#[derive(Debug)]
 pub struct UnrealGenerationInfo {}
 
 impl Deferrable for UnrealGenerationInfo {
     type StreamInfoType = SingleItemStreamInfo;
 }
 
-impl Skippable for UnrealGenerationInfo {
-    fn seek_past_with_info<R>(reader: &mut R, stream_info: &Self::StreamInfoType) -> Result<()>
+impl Parseable for UnrealGenerationInfo {
+    type ParsedType = GenerationInfo;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
     where
         R: Seek + Read,
     {
-        reader.seek(SeekFrom::Start(stream_info.offset + GENERATION_INFO_SIZE))?;
-        Ok(())
+        Ok(Self::ParsedType {
+            export_count: reader.read_le()?,
+            name_count: reader.read_le()?,
+        })
     }
 }
 
-/// Size of `FCompressedChunk`
-const COMPRESSED_CHUNK_SIZE: u64 = 16;
+#[derive(Debug)]
 pub struct UnrealCompressedChunk {}
 
 impl Deferrable for UnrealCompressedChunk {
     type StreamInfoType = SingleItemStreamInfo;
 }
 
-impl Skippable for UnrealCompressedChunk {
-    fn seek_past_with_info<R>(reader: &mut R, stream_info: &Self::StreamInfoType) -> Result<()>
+impl Parseable for UnrealCompressedChunk {
+    type ParsedType = CompressedChunk;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
     where
         R: Seek + Read,
     {
-        reader.seek(SeekFrom::Start(stream_info.offset + COMPRESSED_CHUNK_SIZE))?;
-        Ok(())
+        Ok(Self::ParsedType {
+            uncompressed_offset: reader.read_le()?,
+            uncompressed_size: reader.read_le()?,
+            compressed_offset: reader.read_le()?,
+            compressed_size: reader.read_le()?,
+        })
     }
 }
 
@@ -493,6 +512,192 @@ impl Parseable for UnrealNameReference {
     }
 }
 
+/// The asset path of an `FSoftObjectPath` as it was serialized, before its names are resolved
+#[derive(Debug)]
+pub enum SerializedAssetPath {
+    /// A single name containing the whole path, i.e. `/Game/Package.Asset` (C++ name: `AssetPathName`)
+    Combined(NameReference),
+    /// Separate names for the package & asset, used after `FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES` (C++ name: `FTopLevelAssetPath`)
+    TopLevel {
+        package_name: NameReference,
+        asset_name: NameReference,
+    },
+}
+
+/// An `FSoftObjectPath` as it was serialized, before its names are resolved
+#[derive(Debug)]
+pub struct SerializedSoftObjectPath {
+    pub asset_path: SerializedAssetPath,
+    pub sub_path: String,
+}
+
+#[derive(Debug)]
+pub struct UnrealSoftObjectPath {}
+
+impl Deferrable for UnrealSoftObjectPath {
+    type StreamInfoType = SingleItemStreamInfo;
+}
+
+impl Parseable for UnrealSoftObjectPath {
+    type ParsedType = SerializedSoftObjectPath;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
+    where
+        R: Seek
+            + Read
+            + SerializedObjectVersion<ObjectVersion>
+            + SerializedObjectVersion<ObjectVersionUE5>
+            + SerializedFlags,
+    {
+        // See `FSoftObjectPath::SerializePathWithoutFixup`
+        let asset_path =
+            if reader.serialized_with(ObjectVersionUE5::FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES) {
+                let package_name = UnrealNameReference::parse_inline(reader)?;
+                let asset_name = UnrealNameReference::parse_inline(reader)?;
+                SerializedAssetPath::TopLevel {
+                    package_name,
+                    asset_name,
+                }
+            } else {
+                SerializedAssetPath::Combined(UnrealNameReference::parse_inline(reader)?)
+            };
+        let sub_path = parse_string(reader)?;
+
+        Ok(Self::ParsedType {
+            asset_path,
+            sub_path,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct UnrealAssetRegistryObject {}
+
+impl Deferrable for UnrealAssetRegistryObject {
+    type StreamInfoType = SingleItemStreamInfo;
+}
+
+impl Parseable for UnrealAssetRegistryObject {
+    type ParsedType = AssetRegistryObject;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
+    where
+        R: Seek + Read,
+    {
+        let object_path = parse_string(reader)?;
+        let object_class_name = parse_string(reader)?;
+        let tag_count: i32 = reader.read_le()?;
+        let mut tags = BTreeMap::new();
+        for _ in 0..tag_count {
+            let key = parse_string(reader)?;
+            let value = parse_string(reader)?;
+            tags.insert(key, value);
+        }
+
+        Ok(Self::ParsedType {
+            object_path,
+            object_class_name,
+            tags,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct UnrealBitArray {}
+
+impl Deferrable for UnrealBitArray {
+    type StreamInfoType = SingleItemStreamInfo;
+}
+
+impl Parseable for UnrealBitArray {
+    type ParsedType = Vec<bool>;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
+    where
+        R: Seek + Read,
+    {
+        // `TBitArray` is serialized as the number of bits followed by the bits packed into 32-bit words
+        let num_bits: i32 = reader.read_le()?;
+        let num_bits = num_bits.max(0) as usize;
+        let mut bits = Vec::with_capacity(num_bits);
+        for _ in 0..num_bits.div_ceil(32) {
+            let word: u32 = reader.read_le()?;
+            let bits_in_word = (num_bits - bits.len()).min(32);
+            bits.extend((0..bits_in_word).map(|bit| word.get_bit(bit)));
+        }
+
+        Ok(bits)
+    }
+}
+
+/// An entry in the package build dependencies of the asset registry dependency data, before its name is resolved
+#[derive(Debug)]
+pub struct SerializedPackageBuildDependency {
+    pub package_name: NameReference,
+    pub flags: u32,
+}
+
+#[derive(Debug)]
+pub struct UnrealPackageBuildDependency {}
+
+impl Deferrable for UnrealPackageBuildDependency {
+    type StreamInfoType = SingleItemStreamInfo;
+}
+
+impl Parseable for UnrealPackageBuildDependency {
+    type ParsedType = SerializedPackageBuildDependency;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
+    where
+        R: Seek
+            + Read
+            + SerializedObjectVersion<ObjectVersion>
+            + SerializedObjectVersion<ObjectVersionUE5>
+            + SerializedFlags,
+    {
+        let package_name = UnrealNameReference::parse_inline(reader)?;
+        let flags = reader.read_le()?;
+        Ok(Self::ParsedType {
+            package_name,
+            flags,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct UnrealPackageIndex {}
+
+impl Deferrable for UnrealPackageIndex {
+    type StreamInfoType = SingleItemStreamInfo;
+}
+
+impl Parseable for UnrealPackageIndex {
+    type ParsedType = ObjectReference;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
+    where
+        R: Seek + Read,
+    {
+        let index: i32 = reader.read_le()?;
+        Ok(ObjectReference::from(index))
+    }
+}
+
 #[derive(Debug)]
 pub struct UnrealObjectExport {}
 
@@ -523,7 +728,7 @@ impl Parseable for UnrealObjectExport {
 
         let outer_index = reader.read_le()?;
         let object_name = UnrealNameReference::parse_inline(reader)?;
-        let object_flags = reader.read_le()?;
+        let object_flags = ObjectFlags::from_bits_retain(reader.read_le()?);
 
         let (serial_size, serial_offset) = if reader.serialized_with(ObjectVersion::VER_UE4_64BIT_EXPORTMAP_SERIALSIZES) {
             (reader.read_le()?, reader.read_le()?)
@@ -534,7 +739,7 @@ impl Parseable for UnrealObjectExport {
         let not_for_server = reader.read_le::<u32>()? != 0;
 
         if !reader.serialized_with(ObjectVersionUE5::REMOVE_OBJECT_EXPORT_PACKAGE_GUID) {
-            let _package_guid = UnrealGuid::seek_past(reader)?;
+            let _package_guid = Guid::seek_past(reader)?;
         }
 
         let is_inherited_instance = if reader.serialized_with(ObjectVersionUE5::TRACK_OBJECT_EXPORT_IS_INHERITED) {
@@ -543,7 +748,7 @@ impl Parseable for UnrealObjectExport {
             false
         };
 
-        let package_flags = reader.read_le()?;
+        let package_flags = PackageFlags::from_bits_retain(reader.read_le()?);
 
         let not_always_loaded_for_editor_game = if reader.serialized_with(ObjectVersion::VER_UE4_LOAD_FOR_EDITOR_GAME)
         {
@@ -684,3 +889,45 @@ impl Parseable for UnrealThumbnailInfo {
         })
     }
 }
+
+pub struct UnrealObjectThumbnail {}
+
+impl Deferrable for UnrealObjectThumbnail {
+    type StreamInfoType = SingleItemStreamInfo;
+}
+
+impl Parseable for UnrealObjectThumbnail {
+    type ParsedType = ObjectThumbnail;
+
+    fn parse_with_info_seekless<R>(
+        reader: &mut R,
+        _read_info: &<Self::StreamInfoType as StreamInfo>::ReadInfoType,
+    ) -> Result<Self::ParsedType>
+    where
+        R: Seek + Read,
+    {
+        let width = reader.read_le()?;
+        let height: i32 = reader.read_le()?;
+        // A negative height means the image is a JPEG rather than a PNG (see `FObjectThumbnail::Serialize`)
+        let (height, format) = if height < 0 {
+            (-height, ThumbnailFormat::Jpeg)
+        } else {
+            (height, ThumbnailFormat::Png)
+        };
+
+        let length: i32 = reader.read_le()?;
+        let length = length.max(0) as u64;
+        let mut compressed_image_data = Vec::new();
+        reader.take(length).read_to_end(&mut compressed_image_data)?;
+        if compressed_image_data.len() as u64 != length {
+            return Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        Ok(Self::ParsedType {
+            width,
+            height,
+            format,
+            compressed_image_data,
+        })
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{Error, PropertyTypeName, Result};
+use binread::BinReaderExt;
+use std::{
+    collections::HashMap,
+    io::{Read, Seek},
+};
+
+/// Describes the properties of classes and structs, which is needed to read properties that were serialized without tags (i.e.
+/// packages with [`crate::PackageFlags::UnversionedProperties`], which is the default for cooked content)
+pub trait PropertySchemaProvider {
+    /// Look up the schema of a class or struct by its name, e.g. `Actor` or `MyBlueprint_C`
+    fn struct_schema(&self, struct_name: &str) -> Option<&StructSchema>;
+
+    /// Look up the name of the enum value `value` in the enum `enum_name`, e.g. `EMyEnum::Value`. Enums without a name are read
+    /// as their numeric value.
+    fn enum_value_name(&self, _enum_name: &str, _value: i64) -> Option<&str> {
+        None
+    }
+}
+
+/// The properties of a class or struct, in the order they're declared (C++ name: `UStruct`)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StructSchema {
+    /// Name of the class or struct this inherits from, whose properties come before this struct's properties
+    pub super_struct: Option<String>,
+    /// The properties declared by this struct, not including the ones declared by `super_struct`
+    pub properties: Vec<PropertySchema>,
+}
+
+/// A single property in a [`StructSchema`] (C++ name: `FProperty`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertySchema {
+    /// Name of the property
+    pub name: String,
+    /// Complete type of the property, e.g. `ArrayProperty(StructProperty(Vector))`
+    pub type_name: PropertyTypeName,
+    /// Number of elements if the property is a static array, 1 otherwise (C++ name: `ArrayDim`)
+    pub array_dim: u32,
+}
+
+/// A simple [`PropertySchemaProvider`] that holds schemas in memory
+#[derive(Clone, Debug, Default)]
+pub struct PropertySchemas {
+    structs: HashMap<String, StructSchema>,
+    enums: HashMap<String, HashMap<i64, String>>,
+}
+
+impl PropertySchemas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the schema of a class or struct, replacing any previous schema with the same name
+    pub fn add_struct(&mut self, struct_name: String, schema: StructSchema) {
+        self.structs.insert(struct_name, schema);
+    }
+
+    /// Add the values of an enum, replacing any previous enum with the same name
+    pub fn add_enum<I: IntoIterator<Item = (String, i64)>>(
+        &mut self,
+        enum_name: String,
+        values: I,
+    ) {
+        self.enums.insert(
+            enum_name,
+            values
+                .into_iter()
+                .map(|(name, value)| (value, name))
+                .collect(),
+        );
+    }
+
+    /// Load schemas from JSON, in the format:
+    ///
+    /// ```json
+    /// {
+    ///     "structs": {
+    ///         "MyActor": {
+    ///             "super": "Actor",
+    ///             "properties": [
+    ///                 { "name": "Health", "type": "FloatProperty" },
+    ///                 { "name": "Waypoints", "type": "ArrayProperty(StructProperty(Vector(/Script/CoreUObject)))" },
+    ///                 { "name": "Slots", "type": "ObjectProperty", "array_dim": 4 }
+    ///             ]
+    ///         }
+    ///     },
+    ///     "enums": {
+    ///         "EMyEnum": { "EMyEnum::First": 0, "EMyEnum::Second": 1 }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Multiple files can be loaded into the same [`PropertySchemas`] by calling this repeatedly.
+    #[cfg(feature = "json-schemas")]
+    pub fn load_json<R: Read>(&mut self, reader: R) -> Result<()> {
+        use serde::Deserialize;
+
+        #[derive(Deserialize)]
+        struct JsonSchemas {
+            #[serde(default)]
+            structs: HashMap<String, JsonStruct>,
+            #[serde(default)]
+            enums: HashMap<String, HashMap<String, i64>>,
+        }
+
+        #[derive(Deserialize)]
+        struct JsonStruct {
+            #[serde(rename = "super")]
+            super_struct: Option<String>,
+            #[serde(default)]
+            properties: Vec<JsonProperty>,
+        }
+
+        #[derive(Deserialize)]
+        struct JsonProperty {
+            name: String,
+            #[serde(rename = "type")]
+            type_name: String,
+            #[serde(default = "default_array_dim")]
+            array_dim: u32,
+        }
+
+        fn default_array_dim() -> u32 {
+            1
+        }
+
+        let schemas: JsonSchemas = serde_json::from_reader(reader)
+            .map_err(|error| Error::InvalidSchema(error.to_string()))?;
+
+        for (struct_name, json_struct) in schemas.structs {
+            let properties = json_struct
+                .properties
+                .into_iter()
+                .map(|property| {
+                    Ok(PropertySchema {
+                        type_name: property.type_name.parse().map_err(|error: Error| {
+                            Error::InvalidSchema(format!(
+                                "{}.{}: {}",
+                                struct_name, property.name, error
+                            ))
+                        })?,
+                        name: property.name,
+                        array_dim: property.array_dim,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            self.add_struct(
+                struct_name,
+                StructSchema {
+                    super_struct: json_struct.super_struct,
+                    properties,
+                },
+            );
+        }
+
+        for (enum_name, values) in schemas.enums {
+            self.add_enum(enum_name, values);
+        }
+
+        Ok(())
+    }
+
+    /// Load schemas from a JSON file, see [`PropertySchemas::load_json`] for the format
+    #[cfg(feature = "json-schemas")]
+    pub fn load_json_file<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<()> {
+        self.load_json(std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+}
+
+impl PropertySchemaProvider for PropertySchemas {
+    fn struct_schema(&self, struct_name: &str) -> Option<&StructSchema> {
+        self.structs.get(struct_name)
+    }
+
+    fn enum_value_name(&self, enum_name: &str, value: i64) -> Option<&str> {
+        self.enums
+            .get(enum_name)
+            .and_then(|values| values.get(&value))
+            .map(String::as_str)
+    }
+}
+
+/// Every property of `struct_name` including the ones inherited from its super structs, in the order they're indexed by
+/// unversioned serialization. Static arrays have one entry per element, along with the index of the element.
+pub(crate) fn flatten_schema<'a>(
+    schemas: &'a dyn PropertySchemaProvider,
+    struct_name: &str,
+) -> Result<Vec<(&'a PropertySchema, u32)>> {
+    let mut hierarchy = Vec::new();
+    let mut next_struct = Some(struct_name);
+    while let Some(struct_name) = next_struct {
+        let schema = schemas
+            .struct_schema(struct_name)
+            .ok_or_else(|| Error::MissingSchema(struct_name.to_string()))?;
+        hierarchy.push(schema);
+        if hierarchy.len() > 1024 {
+            return Err(Error::InvalidSchema(format!(
+                "super structs of {} contain a cycle",
+                struct_name
+            )));
+        }
+        next_struct = schema.super_struct.as_deref();
+    }
+
+    Ok(hierarchy
+        .into_iter()
+        .rev()
+        .flat_map(|schema| &schema.properties)
+        .flat_map(|property| {
+            (0..property.array_dim).map(move |array_index| (property, array_index))
+        })
+        .collect())
+}
+
+/// The header of unversioned property data, which describes which properties of the schema are serialized (see `FUnversionedHeader`
+/// in Engine/Source/Runtime/CoreUObject/Private/Serialization/UnversionedPropertySerialization.cpp)
+#[derive(Debug, Default)]
+pub(crate) struct UnversionedHeader {
+    /// Index into the flattened schema of each property that's been serialized, and whether it's zero. Zero properties don't have
+    /// any data after the header.
+    pub properties: Vec<(usize, bool)>,
+}
+
+impl UnversionedHeader {
+    const SKIP_NUM_MASK: u16 = 0x007f;
+    const HAS_ZERO_MASK: u16 = 0x0080;
+    const IS_LAST_MASK: u16 = 0x0100;
+    const VALUE_NUM_SHIFT: u16 = 9;
+
+    pub fn parse<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        // Each fragment is a run of skipped properties followed by a run of serialized properties
+        struct Fragment {
+            skip_num: usize,
+            value_num: usize,
+            has_any_zeroes: bool,
+        }
+
+        let mut fragments = Vec::new();
+        let mut zero_mask_num = 0;
+        loop {
+            let packed: u16 = reader.read_le()?;
+            let fragment = Fragment {
+                skip_num: (packed & Self::SKIP_NUM_MASK) as usize,
+                value_num: (packed >> Self::VALUE_NUM_SHIFT) as usize,
+                has_any_zeroes: packed & Self::HAS_ZERO_MASK != 0,
+            };
+            if fragment.has_any_zeroes {
+                zero_mask_num += fragment.value_num;
+            }
+            fragments.push(fragment);
+
+            if packed & Self::IS_LAST_MASK != 0 {
+                break;
+            }
+        }
+
+        // The zero mask uses the smallest integer type that fits it, with the first property in the lowest bit
+        let zero_mask: Vec<bool> = if zero_mask_num == 0 {
+            Vec::new()
+        } else if zero_mask_num <= 8 {
+            let bits: u8 = reader.read_le()?;
+            (0..zero_mask_num)
+                .map(|bit| bits & (1 << bit) != 0)
+                .collect()
+        } else if zero_mask_num <= 16 {
+            let bits: u16 = reader.read_le()?;
+            (0..zero_mask_num)
+                .map(|bit| bits & (1 << bit) != 0)
+                .collect()
+        } else {
+            let mut zero_mask = Vec::with_capacity(zero_mask_num);
+            for _ in 0..zero_mask_num.div_ceil(32) {
+                let bits: u32 = reader.read_le()?;
+                zero_mask.extend((0..32).map(|bit| bits & (1 << bit) != 0));
+            }
+            zero_mask.truncate(zero_mask_num);
+            zero_mask
+        };
+
+        let mut properties = Vec::new();
+        let mut schema_index = 0;
+        let mut zero_mask = zero_mask.into_iter();
+        for fragment in fragments {
+            schema_index += fragment.skip_num;
+            for _ in 0..fragment.value_num {
+                let is_zero = fragment.has_any_zeroes && zero_mask.next().unwrap_or_default();
+                properties.push((schema_index, is_zero));
+                schema_index += 1;
+            }
+        }
+
+        Ok(Self { properties })
+    }
+}
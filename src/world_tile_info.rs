@@ -0,0 +1,219 @@
+use crate::{
+    ObjectVersion, ObjectVersionUE5, Result,
+    archive::{Archive, SerializedObjectVersion},
+    serialization::{Parseable, UnrealString},
+};
+use binread::BinReaderExt;
+use std::{
+    collections::HashSet,
+    io::{Read, Seek},
+    ops::Add,
+};
+
+/// A vector of integers, used for positions in world composition (C++ name: `FIntVector`)
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntVector {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Add for IntVector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+/// A vector, which is saved with double precision with [`ObjectVersionUE5::LARGE_WORLD_COORDINATES`] or later (C++ name: `FVector`)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// An axis aligned bounding box (C++ name: `FBox`)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BoundingBox {
+    /// (C++ name: `Min`)
+    pub min: Vector,
+    /// (C++ name: `Max`)
+    pub max: Vector,
+    /// False if the box is empty, in which case `min` and `max` are meaningless (C++ name: `IsValid`)
+    pub is_valid: bool,
+}
+
+/// The layer a world composition tile belongs to, which decides when it's streamed in (C++ name: `FWorldTileLayer`)
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WorldTileLayer {
+    /// (C++ name: `Name`)
+    pub name: String,
+    /// Distance from the viewer at which tiles in this layer are streamed in (C++ name: `StreamingDistance`)
+    pub streaming_distance: i32,
+    /// If false, tiles in this layer aren't streamed in based on distance (C++ name: `DistanceStreamingEnabled`)
+    pub distance_streaming_enabled: bool,
+}
+
+/// A level of detail of a world composition tile, which is a separate package with a `_LOD<N>` suffix (C++ name:
+/// `FWorldTileLODInfo`)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WorldTileLodInfo {
+    /// Distance from the viewer at which this LOD is used, relative to the streaming distance of the tile's layer (C++ name:
+    /// `RelativeStreamingDistance`)
+    pub relative_streaming_distance: i32,
+}
+
+/// Information about a map that's a tile in a world composition, which the editor shows in the world composition overview without
+/// loading the map. Read with [`crate::AssetHeader::world_tile_info`]. (C++ name: `FWorldTileInfo`)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WorldTileInfo {
+    /// Position of the tile relative to its parent tile, see [`WorldTileInfo::absolute_position`] (C++ name: `Position`)
+    pub position: IntVector,
+    /// Bounds of the tile, relative to its position (C++ name: `Bounds`)
+    pub bounds: BoundingBox,
+    /// (C++ name: `Layer`)
+    pub layer: WorldTileLayer,
+    /// (C++ name: `bHideInTileView`)
+    pub hide_in_tile_view: bool,
+    /// Package name of the parent tile, or an empty string if this tile doesn't have a parent (C++ name: `ParentTilePackageName`)
+    pub parent_tile_package_name: String,
+    /// (C++ name: `LODList`)
+    pub lod_list: Vec<WorldTileLodInfo>,
+    /// Order the tile is drawn in, in the world composition overview (C++ name: `ZOrder`)
+    pub z_order: i32,
+}
+
+impl WorldTileInfo {
+    /// Read the tile info at the current position of `archive` (see `operator<<(FArchive&, FWorldTileInfo&)`)
+    pub(crate) fn parse<R: Read + Seek>(archive: &mut Archive<R>) -> Result<Self> {
+        // The position used to be an `FIntPoint`, which only has X and Y
+        let position = if archive.serialized_with(ObjectVersion::VER_UE4_WORLD_LEVEL_INFO_UPDATED) {
+            IntVector {
+                x: archive.read_le()?,
+                y: archive.read_le()?,
+                z: archive.read_le()?,
+            }
+        } else {
+            IntVector {
+                x: archive.read_le()?,
+                y: archive.read_le()?,
+                z: 0,
+            }
+        };
+        let bounds = BoundingBox {
+            min: read_vector(archive)?,
+            max: read_vector(archive)?,
+            is_valid: archive.read_le::<u8>()? != 0,
+        };
+
+        let name = UnrealString::parse_inline(archive)?;
+        let _reserved0: i32 = archive.read_le()?;
+        let _reserved1: [i32; 2] = archive.read_le()?;
+        let streaming_distance =
+            if archive.serialized_with(ObjectVersion::VER_UE4_WORLD_LEVEL_INFO_UPDATED) {
+                archive.read_le()?
+            } else {
+                0
+            };
+        let distance_streaming_enabled = if archive
+            .serialized_with(ObjectVersion::VER_UE4_WORLD_LAYER_ENABLE_DISTANCE_STREAMING)
+        {
+            read_bool(archive)?
+        } else {
+            true
+        };
+        let layer = WorldTileLayer {
+            name,
+            streaming_distance,
+            distance_streaming_enabled,
+        };
+
+        let (hide_in_tile_view, parent_tile_package_name) =
+            if archive.serialized_with(ObjectVersion::VER_UE4_WORLD_LEVEL_INFO_UPDATED) {
+                (read_bool(archive)?, UnrealString::parse_inline(archive)?)
+            } else {
+                (false, String::new())
+            };
+
+        let lod_list = if archive.serialized_with(ObjectVersion::VER_UE4_WORLD_LEVEL_INFO_LOD_LIST)
+        {
+            let count: i32 = archive.read_le()?;
+            let mut lod_list = Vec::with_capacity(count.clamp(0, 1024) as usize);
+            for _ in 0..count {
+                let relative_streaming_distance = archive.read_le()?;
+                let _reserved: (f32, f32, i32, i32) = archive.read_le()?;
+                lod_list.push(WorldTileLodInfo {
+                    relative_streaming_distance,
+                });
+            }
+            lod_list
+        } else {
+            Vec::new()
+        };
+
+        let z_order = if archive.serialized_with(ObjectVersion::VER_UE4_WORLD_LEVEL_INFO_ZORDER) {
+            archive.read_le()?
+        } else {
+            0
+        };
+
+        Ok(Self {
+            position,
+            bounds,
+            layer,
+            hide_in_tile_view,
+            parent_tile_package_name,
+            lod_list,
+            z_order,
+        })
+    }
+
+    /// Calculate the position of this tile in the world, by adding the positions of its parent tiles (C++ name:
+    /// `AbsolutePosition`). Packages only store the position relative to the parent, so `find_tile` should look up the info of
+    /// the tile in another package by its package name. Parents that can't be found are treated as being at the origin.
+    pub fn absolute_position<'a, F>(&'a self, find_tile: F) -> IntVector
+    where
+        F: Fn(&str) -> Option<&'a WorldTileInfo>,
+    {
+        let mut absolute_position = self.position;
+        let mut visited = HashSet::new();
+        let mut parent_name = self.parent_tile_package_name.as_str();
+        // Stop if the parents form a cycle, which the editor doesn't allow
+        while !parent_name.is_empty() && visited.insert(parent_name) {
+            let Some(parent) = find_tile(parent_name) else {
+                break;
+            };
+            absolute_position = absolute_position + parent.position;
+            parent_name = &parent.parent_tile_package_name;
+        }
+        absolute_position
+    }
+}
+
+/// Read a `bool` the way `FArchive` serializes it, as a 32-bit integer
+fn read_bool<R: Read + Seek>(archive: &mut Archive<R>) -> Result<bool> {
+    Ok(archive.read_le::<u32>()? != 0)
+}
+
+/// Read a `FVector`, which is saved as doubles with large world coordinates and floats before
+fn read_vector<R: Read + Seek>(archive: &mut Archive<R>) -> Result<Vector> {
+    if archive.serialized_with(ObjectVersionUE5::LARGE_WORLD_COORDINATES) {
+        Ok(Vector {
+            x: archive.read_le()?,
+            y: archive.read_le()?,
+            z: archive.read_le()?,
+        })
+    } else {
+        Ok(Vector {
+            x: archive.read_le::<f32>()? as f64,
+            y: archive.read_le::<f32>()? as f64,
+            z: archive.read_le::<f32>()? as f64,
+        })
+    }
+}
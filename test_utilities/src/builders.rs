@@ -0,0 +1,64 @@
+//! Serialization helpers shared by tests that append data the test assets don't have to real packages, or patch it in
+
+use flate2::{Compression, write::ZlibEncoder};
+use std::io::{Read, Write};
+
+/// Data to store in payloads, which is easy to recognize when it's read back
+pub const PAYLOAD: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+/// Serialize `value` the way `FArchive` serializes an `int32`
+pub fn push_i32(bytes: &mut Vec<u8>, value: i32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Serialize `string` the way `FString` is serialized, as a null terminated ANSI string
+pub fn push_string(bytes: &mut Vec<u8>, string: &str) {
+    push_i32(bytes, string.len() as i32 + 1);
+    bytes.extend_from_slice(string.as_bytes());
+    bytes.push(0);
+}
+
+/// Append `data` to the end of the package in `bytes`, and return the offset it's at
+pub fn append_to_package(bytes: &mut Vec<u8>, data: &[u8]) -> i32 {
+    let offset = bytes.len() as i32;
+    bytes.extend_from_slice(data);
+    offset
+}
+
+/// Compress `data` the way `FArchive::SerializeCompressed` does, in blocks of `block_size`
+pub fn serialize_compressed(data: &[u8], block_size: usize) -> Vec<u8> {
+    let blocks: Vec<_> = data
+        .chunks(block_size)
+        .map(|block| {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(block).unwrap();
+            (encoder.finish().unwrap(), block.len())
+        })
+        .collect();
+    let compressed_size: usize = blocks.iter().map(|(compressed, _)| compressed.len()).sum();
+
+    let mut serialized = Vec::new();
+    for value in [
+        0x9E2A83C1,
+        block_size as i64,
+        compressed_size as i64,
+        data.len() as i64,
+    ] {
+        serialized.extend_from_slice(&i64::to_le_bytes(value));
+    }
+    for (compressed, uncompressed_size) in &blocks {
+        serialized.extend_from_slice(&(compressed.len() as i64).to_le_bytes());
+        serialized.extend_from_slice(&(*uncompressed_size as i64).to_le_bytes());
+    }
+    for (compressed, _) in &blocks {
+        serialized.extend_from_slice(compressed);
+    }
+    serialized
+}
+
+/// Read everything from `reader`, e.g. a bulk data reader
+pub fn read_to_end<R: Read>(mut reader: R) -> Vec<u8> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).unwrap();
+    data
+}
@@ -1,6 +1,9 @@
 pub use rstest_reuse::{self, template};
 use std::path::PathBuf;
 
+mod builders;
+pub use builders::*;
+
 pub use uasset::{ObjectVersion, ObjectVersionUE5};
 
 const LATEST_UE4_MINOR_VERSION: u32 = 27;
@@ -68,6 +71,10 @@ impl UnrealVersion {
         path.set_extension("uasset");
         path
     }
+    /// Read the asset at `ue_path`, to patch or append test data to
+    pub fn read_asset(&self, ue_path: &str) -> Vec<u8> {
+        std::fs::read(self.resolve_ue_path(ue_path)).unwrap()
+    }
 }
 
 #[template]
@@ -1,10 +1,12 @@
-use std::fs::File;
+use std::{collections::HashSet, fs::File};
 
 use ::rstest_reuse::*;
 use rstest::rstest;
 use test_utilities::*;
 
-use uasset::AssetHeader;
+use uasset::{
+    AssetHeader, AssetIndex, MountPoints, ObjectReference, PackageReference, ReferenceKind,
+};
 
 #[apply(all_versions)]
 fn simple_refs(#[case] version_info: UnrealVersionInfo) {
@@ -28,3 +30,331 @@ fn simple_refs(#[case] version_info: UnrealVersionInfo) {
         assert_eq!(&asset_imports, expected_imports);
     }
 }
+
+#[apply(all_versions)]
+fn depends_map(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut package = AssetHeader::new(File::open(asset_path).unwrap()).unwrap();
+    let num_exports = package.exports.len();
+    let num_imports = package.imports.len();
+
+    let depends_map = package.depends_map().unwrap();
+    assert_eq!(depends_map.len(), num_exports);
+    for reference in depends_map.iter().flatten() {
+        match *reference {
+            ObjectReference::Export { export_index } => assert!(export_index < num_exports),
+            ObjectReference::Import { import_index } => assert!(import_index < num_imports),
+            ObjectReference::None => panic!("unexpected null reference in depends map"),
+        }
+    }
+}
+
+#[apply(all_versions)]
+fn soft_refs(#[case] version_info: UnrealVersionInfo) {
+    let expected_refs = [
+        (
+            "/Game/SimpleRefs/SimpleRefsRoot",
+            vec!["/Game/SimpleRefs/SimpleRefsSoftRef"],
+        ),
+        ("/Game/SimpleRefs/SimpleRefsSoftRef", vec![]),
+    ];
+
+    for (asset, expected_soft_refs) in &expected_refs {
+        let asset_path = version_info.version.resolve_ue_path(asset);
+        let mut package = AssetHeader::new(File::open(asset_path).unwrap()).unwrap();
+
+        // Starting with 4.20 packages also record soft references to themselves, which we're not interested in here.
+        let soft_refs: Vec<String> = package
+            .soft_package_references()
+            .unwrap()
+            .into_iter()
+            .filter(|soft_ref| soft_ref != asset)
+            .collect();
+        assert_eq!(&soft_refs, expected_soft_refs, "soft references of {}", asset);
+    }
+}
+
+#[apply(all_versions)]
+fn soft_object_paths(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut package = AssetHeader::new(File::open(asset_path).unwrap()).unwrap();
+    let soft_object_paths = package.soft_object_paths().unwrap();
+
+    let has_soft_object_path_list = version_info
+        .object_version_ue5
+        .is_some_and(|version| version >= ObjectVersionUE5::ADD_SOFTOBJECTPATH_LIST);
+    if has_soft_object_path_list {
+        let soft_ref = soft_object_paths
+            .iter()
+            .find(|path| path.package_name == "/Game/SimpleRefs/SimpleRefsSoftRef")
+            .expect("missing soft object path to SimpleRefsSoftRef");
+        assert_eq!(soft_ref.asset_name, "SimpleRefsSoftRef_C");
+        assert_eq!(
+            soft_ref.to_string(),
+            "/Game/SimpleRefs/SimpleRefsSoftRef.SimpleRefsSoftRef_C"
+        );
+    } else {
+        assert!(soft_object_paths.is_empty());
+    }
+}
+
+#[apply(all_versions)]
+fn object_paths(#[case] version_info: UnrealVersionInfo) {
+    let package_name = "/Game/SimpleRefs/SimpleRefsRoot";
+    let asset_path = version_info.version.resolve_ue_path(package_name);
+    let mut package = AssetHeader::new(File::open(asset_path).unwrap()).unwrap();
+    // Older assets don't record their own package name
+    if package.package_name == "None" {
+        package.package_name = package_name.to_string();
+    }
+
+    let full_names: Vec<String> = (0..package.exports.len())
+        .map(|export_index| ObjectReference::Export { export_index })
+        .chain(
+            (0..package.imports.len()).map(|import_index| ObjectReference::Import { import_index }),
+        )
+        .map(|reference| package.full_name(reference).unwrap())
+        .collect();
+
+    for expected_full_name in [
+        "Blueprint'/Game/SimpleRefs/SimpleRefsRoot.SimpleRefsRoot'",
+        "BlueprintGeneratedClass'/Game/SimpleRefs/SimpleRefsRoot.SimpleRefsRoot_C'",
+        "K2Node_CustomEvent'/Game/SimpleRefs/SimpleRefsRoot.SimpleRefsRoot:EventGraph.K2Node_CustomEvent_1'",
+        "BlueprintGeneratedClass'/Game/SimpleRefs/SimpleRefsDefaultsRef.SimpleRefsDefaultsRef_C'",
+        "Function'/Script/Engine.KismetSystemLibrary:PrintString'",
+        "Package'/Game/SimpleRefs/SimpleRefsGraphRef'",
+    ] {
+        assert!(
+            full_names.iter().any(|full_name| full_name == expected_full_name),
+            "{expected_full_name} not found in {full_names:?}"
+        );
+    }
+
+    assert_eq!(package.object_path(ObjectReference::None).unwrap(), "");
+    assert!(package
+        .object_path(ObjectReference::Export {
+            export_index: package.exports.len()
+        })
+        .is_err());
+}
+
+#[apply(all_versions)]
+fn asset_index(#[case] version_info: UnrealVersionInfo) {
+    let base_path = version_info.version.get_asset_base_path();
+    let mut mount_points = MountPoints::new();
+    mount_points.mount("/Game/", &base_path).unwrap();
+    let (mut index, failures) = AssetIndex::scan(&mount_points);
+    assert!(failures.is_empty(), "failed to index {:?}", failures);
+
+    let root = "/Game/SimpleRefs/SimpleRefsRoot";
+    let root_path = version_info.version.resolve_ue_path(root);
+    assert_eq!(index.package_name_for_path(&root_path), Some(root));
+    assert_eq!(
+        index.package(root).unwrap().path,
+        root_path.canonicalize().unwrap()
+    );
+
+    let hard_referencer = PackageReference {
+        package_name: root.to_string(),
+        kind: ReferenceKind::Hard,
+    };
+    assert_eq!(
+        index.referencers("/Game/SimpleRefs/SimpleRefsDefaultsRef"),
+        std::slice::from_ref(&hard_referencer)
+    );
+    assert_eq!(
+        index.referencers("/Game/SimpleRefs/SimpleRefsGraphRef"),
+        [hard_referencer]
+    );
+    assert_eq!(
+        index.referencers("/Game/SimpleRefs/SimpleRefsSoftRef"),
+        [PackageReference {
+            package_name: root.to_string(),
+            kind: ReferenceKind::Soft,
+        }]
+    );
+    assert!(index.referencers(root).is_empty());
+
+    assert!(index
+        .dependencies(root)
+        .iter()
+        .any(|dependency| dependency.package_name == "/Game/SimpleRefs/SimpleRefsSoftRef"
+            && dependency.kind == ReferenceKind::Soft));
+
+    index.remove_package(root);
+    assert!(index
+        .referencers("/Game/SimpleRefs/SimpleRefsDefaultsRef")
+        .is_empty());
+    assert_eq!(index.package_name_for_path(&root_path), None);
+
+    // Assets outside of the mount points aren't indexed
+    let mut index = AssetIndex::new();
+    let failures = index.add_assets(&MountPoints::new(), [&root_path]);
+    assert!(failures.is_empty(), "failed to index {:?}", failures);
+    assert_eq!(index.packages().count(), 0);
+}
+
+#[apply(all_versions)]
+fn find_cycles(#[case] version_info: UnrealVersionInfo) {
+    let base_path = version_info.version.get_asset_base_path();
+    let mut mount_points = MountPoints::new();
+    mount_points.mount("/Game/", &base_path).unwrap();
+    let (mut index, failures) = AssetIndex::scan(&mount_points);
+    assert!(failures.is_empty(), "failed to index {:?}", failures);
+
+    let expected_cycles = [
+        vec![
+            "/Game/DirectCycle/DirectCycleA",
+            "/Game/DirectCycle/DirectCycleB",
+        ],
+        vec![
+            "/Game/IndirectCycle/IndirectCycleA",
+            "/Game/IndirectCycle/IndirectCycleB",
+            "/Game/IndirectCycle/IndirectCycleC",
+        ],
+    ];
+    assert_eq!(index.find_cycles(false), expected_cycles);
+    assert_eq!(index.find_cycles(true), expected_cycles);
+
+    index.remove_package("/Game/IndirectCycle/IndirectCycleB");
+    assert_eq!(index.find_cycles(false), expected_cycles[..1]);
+}
+
+#[apply(all_versions)]
+fn mount_points(#[case] version_info: UnrealVersionInfo) {
+    let base_path = version_info.version.get_asset_base_path();
+    let mut mount_points = MountPoints::new();
+    mount_points
+        .mount("/Game/", base_path.join("SimpleRefs"))
+        .unwrap();
+    mount_points
+        .mount("/Cycles", base_path.join("DirectCycle"))
+        .unwrap();
+    assert!(
+        mount_points
+            .mount("/Missing/", base_path.join("Missing"))
+            .is_err()
+    );
+
+    let mounts: Vec<_> = mount_points
+        .mounts()
+        .map(|(mount_point, _)| mount_point)
+        .collect();
+    assert_eq!(mounts, ["/Game/", "/Cycles/"]);
+
+    assert_eq!(
+        mount_points
+            .package_name(base_path.join("SimpleRefs/SimpleRefsRoot.uasset"))
+            .as_deref(),
+        Some("/Game/SimpleRefsRoot")
+    );
+    assert_eq!(
+        mount_points
+            .package_name(base_path.join("DirectCycle/DirectCycleA.uasset"))
+            .as_deref(),
+        Some("/Cycles/DirectCycleA")
+    );
+    // Assets don't need to exist to have a package name, but they need to be in a mounted directory
+    assert_eq!(
+        mount_points
+            .package_name(base_path.join("DirectCycle/Deleted.uasset"))
+            .as_deref(),
+        Some("/Cycles/Deleted")
+    );
+    assert_eq!(
+        mount_points.package_name(base_path.join("IndirectCycle/IndirectCycleA.uasset")),
+        None
+    );
+
+    assert_eq!(
+        mount_points.package_exists("/Game/SimpleRefsSoftRef"),
+        Some(true)
+    );
+    assert_eq!(
+        mount_points.package_exists("/Cycles/DirectCycleB"),
+        Some(true)
+    );
+    assert_eq!(mount_points.package_exists("/Cycles/Deleted"), Some(false));
+    assert_eq!(mount_points.package_exists("/Script/CoreUObject"), None);
+
+    // Package names are case insensitive, even if the filesystem isn't
+    assert_eq!(
+        mount_points.package_exists("/cycles/directcycleb"),
+        Some(true)
+    );
+    mount_points.mount("/Project/", &base_path).unwrap();
+    assert_eq!(
+        mount_points.package_exists("/Project/simplerefs/SIMPLEREFSROOT"),
+        Some(true)
+    );
+    assert_eq!(
+        mount_points.package_exists("/Project/simplerefs/Deleted"),
+        Some(false)
+    );
+    assert_eq!(
+        mount_points.package_exists("/Project/Missing/SimpleRefsRoot"),
+        Some(false)
+    );
+}
+
+#[apply(all_versions)]
+fn missing_references(#[case] version_info: UnrealVersionInfo) {
+    let base_path = version_info.version.get_asset_base_path();
+    let root = "/Game/SimpleRefs/SimpleRefsRoot";
+    let root_path = version_info.version.resolve_ue_path(root);
+    let mut package = AssetHeader::new(File::open(root_path).unwrap()).unwrap();
+    let no_deleted_packages = HashSet::new();
+
+    // All references are present when the project is mounted where it's expected
+    let mut mount_points = MountPoints::new();
+    mount_points.mount("/Game/", &base_path).unwrap();
+    assert_eq!(
+        mount_points
+            .missing_references(root, &mut package, &no_deleted_packages)
+            .unwrap(),
+        []
+    );
+
+    // Packages that are being deleted are missing, even though they're still on disk
+    let deleted_packages = HashSet::from([
+        "/Game/SimpleRefs/SimpleRefsGraphRef".to_string(),
+        "/Game/SimpleRefs/SimpleRefsSoftRef".to_string(),
+    ]);
+    let mut missing_references = mount_points
+        .missing_references(root, &mut package, &deleted_packages)
+        .unwrap();
+    missing_references.sort_by(|a, b| a.package_name.cmp(&b.package_name));
+    assert_eq!(
+        missing_references,
+        [
+            PackageReference {
+                package_name: "/Game/SimpleRefs/SimpleRefsGraphRef".to_string(),
+                kind: ReferenceKind::Hard,
+            },
+            PackageReference {
+                package_name: "/Game/SimpleRefs/SimpleRefsSoftRef".to_string(),
+                kind: ReferenceKind::Soft,
+            },
+        ]
+    );
+
+    // Every reference to `/Game/` is missing when it's mounted somewhere without the referenced assets, while `/Script/` isn't
+    // mounted so those references are assumed to exist
+    let mut mount_points = MountPoints::new();
+    mount_points
+        .mount("/Game/", base_path.join("DirectCycle"))
+        .unwrap();
+    let missing_references = mount_points
+        .missing_references(root, &mut package, &no_deleted_packages)
+        .unwrap();
+    assert_eq!(missing_references.len(), 3);
+    assert!(
+        missing_references
+            .iter()
+            .all(|reference| reference.package_name.starts_with("/Game/SimpleRefs/"))
+    );
+}
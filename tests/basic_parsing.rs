@@ -4,7 +4,9 @@ use ::rstest_reuse::*;
 use rstest::rstest;
 use test_utilities::*;
 
-use uasset::AssetHeader;
+use uasset::{
+    AssetHeader, KnownCustomVersion, ObjectFlags, PackageFlags, SerializedCustomVersion,
+};
 
 #[apply(all_versions)]
 fn loading_asset(#[case] version_info: UnrealVersionInfo) {
@@ -59,3 +61,109 @@ fn upgrading_asset(#[case] version_info: UnrealVersionInfo) {
     );
     assert_eq!(new_header.package_source, old_header.package_source);
 }
+
+#[apply(all_versions)]
+fn custom_versions(#[case] version_info: UnrealVersionInfo) {
+    let mut simple_refs_root = version_info.version.get_asset_base_path();
+    simple_refs_root.push("SimpleRefs");
+    simple_refs_root.push("SimpleRefsRoot.uasset");
+    let header = AssetHeader::new(File::open(simple_refs_root).unwrap()).unwrap();
+
+    for custom_version in header.custom_versions() {
+        assert_eq!(
+            header.archive.custom_version(custom_version.guid),
+            Some(custom_version.version)
+        );
+    }
+
+    let ue5_main_stream = KnownCustomVersion::UE5MainStreamObjectVersion.guid();
+    assert_eq!(
+        header
+            .archive
+            .serialized_with_custom_version(ue5_main_stream, 0),
+        version_info.object_version_ue5.is_some()
+    );
+}
+
+#[apply(all_versions)]
+fn package_guids(#[case] version_info: UnrealVersionInfo) {
+    let mut simple_refs_root = version_info.version.get_asset_base_path();
+    simple_refs_root.push("SimpleRefs");
+    simple_refs_root.push("SimpleRefsRoot.uasset");
+    let header = AssetHeader::new(File::open(simple_refs_root).unwrap()).unwrap();
+
+    assert!(!header.guid.is_zero());
+
+    // The test assets were created in 4.10 and upgraded since, so the persistent GUID is the GUID the asset was first saved with
+    let has_package_owner =
+        version_info.object_version >= ObjectVersion::VER_UE4_ADDED_PACKAGE_OWNER;
+    assert_eq!(
+        header.persistent_guid.map(|guid| guid.to_string()),
+        has_package_owner.then(|| "81CE03414B6D77051A7DF8BB96AF4F21".to_string())
+    );
+    let has_owner_persistent_guid = has_package_owner
+        && version_info.object_version < ObjectVersion::VER_UE4_NON_OUTER_PACKAGE_IMPORT;
+    assert_eq!(
+        header.owner_persistent_guid.is_some(),
+        has_owner_persistent_guid
+    );
+
+    let generation = header.generations.last().unwrap();
+    assert_eq!(generation.export_count as usize, header.exports.len());
+    assert_eq!(generation.name_count as usize, header.names.len());
+}
+
+#[apply(all_versions)]
+fn object_flags(#[case] version_info: UnrealVersionInfo) {
+    let mut simple_refs_root = version_info.version.get_asset_base_path();
+    simple_refs_root.push("SimpleRefs");
+    simple_refs_root.push("SimpleRefsRoot.uasset");
+    let header = AssetHeader::new(File::open(simple_refs_root).unwrap()).unwrap();
+
+    assert!(!header.package_flags.contains(PackageFlags::FilterEditorOnly));
+
+    let asset_name = header.find_name("SimpleRefsRoot").unwrap();
+    let asset_export = header
+        .exports
+        .iter()
+        .find(|export| export.object_name == asset_name)
+        .unwrap();
+    assert!(
+        asset_export
+            .object_flags
+            .contains(ObjectFlags::Public | ObjectFlags::Standalone)
+    );
+}
+
+#[apply(all_versions)]
+fn asset_registry_data(#[case] version_info: UnrealVersionInfo) {
+    let mut simple_refs_root = version_info.version.get_asset_base_path();
+    simple_refs_root.push("SimpleRefs");
+    simple_refs_root.push("SimpleRefsRoot.uasset");
+    let mut header = AssetHeader::new(File::open(simple_refs_root).unwrap()).unwrap();
+    let num_imports = header.imports.len();
+    let asset_registry_data = header.asset_registry_data().unwrap();
+
+    let blueprint = asset_registry_data
+        .objects
+        .iter()
+        .find(|object| object.object_path == "SimpleRefsRoot")
+        .expect("missing asset registry data for the blueprint");
+    assert!(blueprint.object_class_name.ends_with("Blueprint"));
+    assert!(
+        blueprint
+            .tags
+            .get("ParentClass")
+            .is_some_and(|parent_class| parent_class.contains("Object"))
+    );
+
+    let has_dependency_data =
+        version_info.object_version >= ObjectVersion::VER_UE4_ASSETREGISTRY_DEPENDENCYFLAGS;
+    match asset_registry_data.dependency_data {
+        Some(dependency_data) => {
+            assert!(has_dependency_data);
+            assert_eq!(dependency_data.import_used_in_game.len(), num_imports);
+        }
+        None => assert!(!has_dependency_data),
+    }
+}
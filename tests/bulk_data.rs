@@ -0,0 +1,149 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, AssetPackage, BulkDataFile, BulkDataFlags, BulkDataLocation};
+
+/// Serialize a bulk data header with 32-bit sizes, in the format used before UE5.3
+fn bulk_data_header(flags: BulkDataFlags, size_on_disk: i32, offset_in_file: i64) -> Vec<u8> {
+    let mut header = Vec::new();
+    header.extend_from_slice(&flags.bits().to_le_bytes());
+    header.extend_from_slice(&(PAYLOAD.len() as i32).to_le_bytes());
+    header.extend_from_slice(&size_on_disk.to_le_bytes());
+    header.extend_from_slice(&offset_in_file.to_le_bytes());
+    header
+}
+
+#[apply(all_versions)]
+fn bulk_data_payloads(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut bytes = std::fs::read(&asset_path).unwrap();
+
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    // Newer versions refer to the data resource table instead, which is covered by `data_resources.rs`
+    if version_info.object_version_ue5 >= Some(ObjectVersionUE5::DATA_RESOURCES) {
+        assert!(package.read_bulk_data_header(0).is_err());
+        return;
+    }
+
+    // Overwrite the largest export with the bulk data we want to read
+    let export = package
+        .exports
+        .iter()
+        .max_by_key(|export| export.serial_size)
+        .unwrap();
+    let (serial_offset, serial_size) = (export.serial_offset as usize, export.serial_size as usize);
+    let bulk_data_start_offset = package.bulk_data_start_offset;
+
+    let inline_offset = serial_offset;
+    let mut inline = bulk_data_header(BulkDataFlags::empty(), PAYLOAD.len() as i32, 0);
+    inline.extend_from_slice(PAYLOAD);
+
+    let compressed_offset = inline_offset + inline.len();
+    let compressed_payload = serialize_compressed(PAYLOAD, 128 * 1024);
+    let mut compressed = bulk_data_header(
+        BulkDataFlags::SerializeCompressedZLIB,
+        compressed_payload.len() as i32,
+        0,
+    );
+    compressed.extend_from_slice(&compressed_payload);
+
+    // The payload at the "end of the file" is after the header that refers to it, with an offset relative to the bulk data
+    let end_of_file_offset = compressed_offset + compressed.len();
+    let end_of_file_payload_offset = end_of_file_offset + 20;
+    let end_of_file = bulk_data_header(
+        BulkDataFlags::PayloadAtEndOfFile,
+        PAYLOAD.len() as i32,
+        end_of_file_payload_offset as i64 - bulk_data_start_offset,
+    );
+    assert_eq!(end_of_file.len(), 20);
+
+    let separate_file_offset = end_of_file_payload_offset + PAYLOAD.len();
+    let separate_file = bulk_data_header(
+        BulkDataFlags::PayloadAtEndOfFile
+            | BulkDataFlags::PayloadInSeperateFile
+            | BulkDataFlags::NoOffsetFixUp,
+        PAYLOAD.len() as i32,
+        4,
+    );
+    assert!(separate_file_offset + separate_file.len() <= serial_offset + serial_size);
+
+    for (offset, data) in [
+        (inline_offset, inline.as_slice()),
+        (compressed_offset, &compressed),
+        (end_of_file_offset, &end_of_file),
+        (end_of_file_payload_offset, PAYLOAD),
+        (separate_file_offset, &separate_file),
+    ] {
+        bytes[offset..offset + data.len()].copy_from_slice(data);
+    }
+    package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+
+    let header = package.read_bulk_data_header(inline_offset as u64).unwrap();
+    assert_eq!(header.location(), BulkDataLocation::Inline);
+    assert_eq!(header.element_count, PAYLOAD.len() as i64);
+    assert_eq!(header.offset_in_file, inline_offset as i64 + 20);
+    assert_eq!(
+        read_to_end(package.bulk_data_reader(&header).unwrap()),
+        PAYLOAD
+    );
+
+    let header = package
+        .read_bulk_data_header(compressed_offset as u64)
+        .unwrap();
+    assert_eq!(header.location(), BulkDataLocation::Inline);
+    assert!(header.is_compressed());
+    assert_eq!(
+        read_to_end(package.bulk_data_reader(&header).unwrap()),
+        PAYLOAD
+    );
+
+    let header = package
+        .read_bulk_data_header(end_of_file_offset as u64)
+        .unwrap();
+    assert_eq!(header.location(), BulkDataLocation::EndOfFile);
+    assert_eq!(header.offset_in_file, end_of_file_payload_offset as i64);
+    assert_eq!(
+        read_to_end(package.bulk_data_reader(&header).unwrap()),
+        PAYLOAD
+    );
+
+    let header = package
+        .read_bulk_data_header(separate_file_offset as u64)
+        .unwrap();
+    assert_eq!(
+        header.location(),
+        BulkDataLocation::SeparateFile(BulkDataFile::Bulk)
+    );
+    assert!(package.bulk_data_reader(&header).is_err());
+
+    // Payloads in separate files need to be read through a package that knows where it's located
+    let package_directory = std::env::temp_dir().join(format!(
+        "uasset-bulk-data-{}.{}-{}",
+        version_info.version.0,
+        version_info.version.1,
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&package_directory).unwrap();
+    let package_path = package_directory.join("SimpleRefsRoot.uasset");
+    std::fs::write(&package_path, &bytes).unwrap();
+    let mut bulk_file = b"ABCD".to_vec();
+    bulk_file.extend_from_slice(PAYLOAD);
+    std::fs::write(package_directory.join("SimpleRefsRoot.ubulk"), bulk_file).unwrap();
+
+    assert_eq!(
+        header.separate_file_path(&package_path),
+        Some(package_directory.join("SimpleRefsRoot.ubulk"))
+    );
+    let mut package = AssetPackage::open(&package_path).unwrap();
+    assert_eq!(
+        read_to_end(package.bulk_data_reader(&header).unwrap()),
+        PAYLOAD
+    );
+
+    std::fs::remove_dir_all(package_directory).unwrap();
+}
@@ -0,0 +1,139 @@
+use std::io::{Cursor, Seek, SeekFrom};
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, CompressedChunk, CompressionFlags, Error};
+
+/// Size of the blocks that chunks are split into when they're compressed
+const BLOCK_SIZE: usize = 4096;
+
+fn serialize_string(string: &str) -> Vec<u8> {
+    let mut serialized = Vec::new();
+    push_string(&mut serialized, string);
+    serialized
+}
+
+fn find(bytes: &[u8], needle: &[u8], start: usize) -> usize {
+    start
+        + bytes[start..]
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .unwrap()
+}
+
+#[apply(all_versions)]
+fn compressed_package(#[case] version_info: UnrealVersionInfo) {
+    let bytes = version_info
+        .version
+        .read_asset("/Game/SimpleRefs/SimpleRefsRoot");
+
+    let mut header = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(header.compression_flags, CompressionFlags::empty());
+    assert!(header.compressed_chunks.is_empty());
+    let uncompressed = AssetHeader::new_compressed(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(uncompressed.names, header.names);
+
+    // Make room for two chunks in the package summary by shortening the branch names of the engine versions, so nothing moves
+    let branch_name = &header.engine_version.branch_name;
+    let compatible_branch_name = &header.compatible_with_engine_version.branch_name;
+    let short_branch_name = &branch_name[..branch_name.len() - 16];
+    let short_compatible_branch_name = &compatible_branch_name[..compatible_branch_name.len() - 16];
+    let branch_name = serialize_string(branch_name);
+    let compatible_branch_name = serialize_string(compatible_branch_name);
+    let branch_name_position = find(&bytes, &branch_name, 0);
+    let compatible_branch_name_position = find(
+        &bytes,
+        &compatible_branch_name,
+        branch_name_position + branch_name.len(),
+    );
+    let chunk_count_position = compatible_branch_name_position + compatible_branch_name.len() + 4;
+    let name_table_position = find(
+        &bytes,
+        &serialize_string(&header.names[0]),
+        chunk_count_position,
+    );
+
+    let mut package = bytes[..branch_name_position].to_vec();
+    push_string(&mut package, short_branch_name);
+    package.extend_from_slice(
+        &bytes[branch_name_position + branch_name.len()..compatible_branch_name_position],
+    );
+    push_string(&mut package, short_compatible_branch_name);
+    let compression_flags_position = package.len();
+    package.extend_from_slice(&CompressionFlags::ZLIB.bits().to_le_bytes());
+    package.extend_from_slice(&2i32.to_le_bytes());
+    let chunk_table_position = package.len();
+    package.extend_from_slice(&[0; 32]);
+    package.extend_from_slice(&bytes[chunk_count_position + 4..]);
+    assert_eq!(package.len(), bytes.len());
+
+    // Compress everything after the package summary in two chunks
+    let middle = name_table_position + (package.len() - name_table_position) / 2;
+    let first_chunk = serialize_compressed(&package[name_table_position..middle], BLOCK_SIZE);
+    let second_chunk = serialize_compressed(&package[middle..], BLOCK_SIZE);
+    let compressed_chunks = [
+        CompressedChunk {
+            uncompressed_offset: name_table_position as i32,
+            uncompressed_size: (middle - name_table_position) as i32,
+            compressed_offset: name_table_position as i32,
+            compressed_size: first_chunk.len() as i32,
+        },
+        CompressedChunk {
+            uncompressed_offset: middle as i32,
+            uncompressed_size: (package.len() - middle) as i32,
+            compressed_offset: (name_table_position + first_chunk.len()) as i32,
+            compressed_size: second_chunk.len() as i32,
+        },
+    ];
+    let mut chunk_table = Vec::new();
+    for chunk in &compressed_chunks {
+        for value in [
+            chunk.uncompressed_offset,
+            chunk.uncompressed_size,
+            chunk.compressed_offset,
+            chunk.compressed_size,
+        ] {
+            chunk_table.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    package[chunk_table_position..chunk_table_position + 32].copy_from_slice(&chunk_table);
+
+    let mut compressed_bytes = package[..name_table_position].to_vec();
+    compressed_bytes.extend_from_slice(&first_chunk);
+    compressed_bytes.extend_from_slice(&second_chunk);
+
+    assert!(matches!(
+        AssetHeader::new(Cursor::new(compressed_bytes.clone())),
+        Err(Error::CompressedPackage)
+    ));
+
+    let mut compressed =
+        AssetHeader::new_compressed(Cursor::new(compressed_bytes.clone())).unwrap();
+    assert_eq!(compressed.compression_flags, CompressionFlags::ZLIB);
+    assert_eq!(compressed.compressed_chunks, compressed_chunks);
+    assert_eq!(compressed.engine_version.branch_name, short_branch_name);
+    assert_eq!(compressed.names, header.names);
+    assert_eq!(compressed.imports.len(), header.imports.len());
+    assert_eq!(compressed.exports.len(), header.exports.len());
+    for export_index in (0..header.exports.len()).rev() {
+        assert_eq!(
+            compressed.export_data(export_index).unwrap(),
+            header.export_data(export_index).unwrap()
+        );
+    }
+
+    let mut reader = compressed.archive.reader;
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    assert_eq!(read_to_end(&mut reader), package);
+    assert_eq!(reader.seek(SeekFrom::End(0)).unwrap(), package.len() as u64);
+
+    // Only zlib compression is supported
+    compressed_bytes[compression_flags_position..compression_flags_position + 4]
+        .copy_from_slice(&CompressionFlags::GZIP.bits().to_le_bytes());
+    assert!(matches!(
+        AssetHeader::new_compressed(Cursor::new(compressed_bytes)),
+        Err(Error::InvalidCompressedPackage(_))
+    ));
+}
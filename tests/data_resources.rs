@@ -0,0 +1,214 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{
+    AssetHeader, BulkDataFile, BulkDataFlags, BulkDataLocation, ObjectDataResource,
+    ObjectDataResourceFlags, ObjectReference,
+};
+
+/// Serialize a data resource table the way `FObjectDataResource::Serialize` does
+pub fn data_resource_table(version: u32, resources: &[ObjectDataResource]) -> Vec<u8> {
+    let mut table = Vec::new();
+    table.extend_from_slice(&version.to_le_bytes());
+    table.extend_from_slice(&(resources.len() as i32).to_le_bytes());
+    for resource in resources {
+        table.extend_from_slice(&resource.flags.bits().to_le_bytes());
+        if version >= 2 {
+            table.push(resource.cooked_index);
+        }
+        for value in [
+            resource.serial_offset,
+            resource.duplicate_serial_offset,
+            resource.serial_size,
+            resource.raw_size,
+        ] {
+            table.extend_from_slice(&value.to_le_bytes());
+        }
+        let outer_index = match resource.outer {
+            ObjectReference::None => 0,
+            ObjectReference::Export { export_index } => export_index as i32 + 1,
+            ObjectReference::Import { import_index } => -(import_index as i32) - 1,
+        };
+        table.extend_from_slice(&outer_index.to_le_bytes());
+        table.extend_from_slice(&resource.legacy_bulk_data_flags.bits().to_le_bytes());
+    }
+    table
+}
+
+/// Point the `DataResourceOffset` at the end of the summary to `data_resource_offset`, and return the patched package
+fn set_data_resource_offset(
+    bytes: &mut [u8],
+    data_resource_offset: i32,
+) -> AssetHeader<Cursor<Vec<u8>>> {
+    // The data resource offset is serialized right after the payload table of contents offset
+    let package = AssetHeader::new(Cursor::new(&*bytes)).unwrap();
+    let payload_toc_offset = package.payload_toc_offset.to_le_bytes();
+    let field_offset = bytes
+        .windows(payload_toc_offset.len())
+        .position(|window| window == payload_toc_offset)
+        .unwrap()
+        + payload_toc_offset.len();
+    bytes[field_offset..field_offset + 4].copy_from_slice(&data_resource_offset.to_le_bytes());
+
+    let package = AssetHeader::new(Cursor::new(bytes.to_vec())).unwrap();
+    assert_eq!(package.data_resource_offset, Some(data_resource_offset));
+    package
+}
+
+#[apply(all_versions)]
+fn data_resource_table_entries(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut bytes = std::fs::read(&asset_path).unwrap();
+
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    assert!(package.data_resources().unwrap().is_empty());
+    assert!(package.export_data_resources(0).unwrap().is_empty());
+    if version_info.object_version_ue5 < Some(ObjectVersionUE5::DATA_RESOURCES) {
+        return;
+    }
+
+    // Overwrite the largest export with references to the data resources we add
+    let export_index = package
+        .exports
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, export)| export.serial_size)
+        .map(|(export_index, _)| export_index)
+        .unwrap();
+    let other_export_index = (export_index + 1) % package.exports.len();
+    let serial_offset = package.exports[export_index].serial_offset as usize;
+    let bulk_data_start_offset = package.bulk_data_start_offset;
+
+    let inline_offset = serial_offset;
+    let end_of_file_offset = inline_offset + 4 + PAYLOAD.len();
+    let end_of_file_payload_offset = end_of_file_offset + 4;
+    let separate_file_offset = end_of_file_payload_offset + PAYLOAD.len();
+    let invalid_offset = separate_file_offset + 4;
+    assert!(
+        invalid_offset + 4 <= serial_offset + package.exports[export_index].serial_size as usize
+    );
+
+    let mut export_data = Vec::new();
+    export_data.extend_from_slice(&0i32.to_le_bytes());
+    export_data.extend_from_slice(PAYLOAD);
+    export_data.extend_from_slice(&1i32.to_le_bytes());
+    export_data.extend_from_slice(PAYLOAD);
+    export_data.extend_from_slice(&2i32.to_le_bytes());
+    export_data.extend_from_slice(&3i32.to_le_bytes());
+    bytes[serial_offset..serial_offset + export_data.len()].copy_from_slice(&export_data);
+
+    let resources = [
+        ObjectDataResource {
+            flags: ObjectDataResourceFlags::Inline,
+            cooked_index: 0,
+            serial_offset: 0,
+            duplicate_serial_offset: -1,
+            serial_size: PAYLOAD.len() as i64,
+            raw_size: PAYLOAD.len() as i64,
+            outer: ObjectReference::Export { export_index },
+            legacy_bulk_data_flags: BulkDataFlags::ForceInlinePayload,
+        },
+        ObjectDataResource {
+            flags: ObjectDataResourceFlags::empty(),
+            cooked_index: 1,
+            serial_offset: end_of_file_payload_offset as i64 - bulk_data_start_offset,
+            duplicate_serial_offset: -1,
+            serial_size: PAYLOAD.len() as i64,
+            raw_size: PAYLOAD.len() as i64,
+            outer: ObjectReference::Export { export_index },
+            legacy_bulk_data_flags: BulkDataFlags::PayloadAtEndOfFile,
+        },
+        ObjectDataResource {
+            flags: ObjectDataResourceFlags::Streaming,
+            cooked_index: 0,
+            serial_offset: 4,
+            duplicate_serial_offset: -1,
+            serial_size: 1024,
+            raw_size: 4096,
+            outer: ObjectReference::Export {
+                export_index: other_export_index,
+            },
+            legacy_bulk_data_flags: BulkDataFlags::PayloadAtEndOfFile
+                | BulkDataFlags::PayloadInSeperateFile
+                | BulkDataFlags::NoOffsetFixUp
+                | BulkDataFlags::SerializeCompressedZLIB,
+        },
+    ];
+
+    // Append the table to the end of the package
+    let data_resource_offset = bytes.len();
+    bytes.extend_from_slice(&data_resource_table(2, &resources));
+    let mut package = set_data_resource_offset(&mut bytes, data_resource_offset as i32);
+
+    assert_eq!(package.data_resources().unwrap(), resources);
+    let owned_resources = package.export_data_resources(export_index).unwrap();
+    if other_export_index == export_index {
+        assert_eq!(owned_resources, resources);
+    } else {
+        assert_eq!(owned_resources, resources[..2]);
+        assert_eq!(
+            package.export_data_resources(other_export_index).unwrap(),
+            resources[2..]
+        );
+    }
+    assert!(
+        package
+            .export_data_resources(package.exports.len())
+            .is_err()
+    );
+
+    let header = package.read_bulk_data_header(inline_offset as u64).unwrap();
+    assert_eq!(header.location(), BulkDataLocation::Inline);
+    assert_eq!(header.offset_in_file, inline_offset as i64 + 4);
+    assert_eq!(
+        read_to_end(package.bulk_data_reader(&header).unwrap()),
+        PAYLOAD
+    );
+
+    let header = package
+        .read_bulk_data_header(end_of_file_offset as u64)
+        .unwrap();
+    assert_eq!(header.location(), BulkDataLocation::EndOfFile);
+    assert_eq!(header.offset_in_file, end_of_file_payload_offset as i64);
+    assert_eq!(
+        read_to_end(package.bulk_data_reader(&header).unwrap()),
+        PAYLOAD
+    );
+
+    let header = package
+        .read_bulk_data_header(separate_file_offset as u64)
+        .unwrap();
+    assert_eq!(
+        header.location(),
+        BulkDataLocation::SeparateFile(BulkDataFile::Bulk)
+    );
+    assert!(header.is_compressed());
+    assert_eq!(header.offset_in_file, 4);
+    assert_eq!(header.size_on_disk, 1024);
+    assert_eq!(header.element_count, 4096);
+
+    assert!(
+        package
+            .read_bulk_data_header(invalid_offset as u64)
+            .is_err()
+    );
+
+    // The initial version of the table doesn't have cooked indices, and later versions aren't supported
+    let mut initial_resources = resources;
+    for resource in &mut initial_resources {
+        resource.cooked_index = 0;
+    }
+    bytes.truncate(data_resource_offset);
+    bytes.extend_from_slice(&data_resource_table(1, &initial_resources));
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(package.data_resources().unwrap(), initial_resources);
+
+    bytes[data_resource_offset..data_resource_offset + 4].copy_from_slice(&3u32.to_le_bytes());
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    assert!(package.data_resources().is_err());
+}
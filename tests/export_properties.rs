@@ -0,0 +1,233 @@
+use std::{fs::File, io::Cursor};
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, ObjectReference, PropertyValue, SoftObjectPath, Text};
+
+#[apply(all_versions)]
+fn default_object_properties(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut package = AssetHeader::new(File::open(asset_path).unwrap()).unwrap();
+
+    let default_object_index = package
+        .exports
+        .iter()
+        .position(|export| {
+            package.resolve_name(&export.object_name).unwrap() == "Default__SimpleRefsRoot_C"
+        })
+        .unwrap();
+
+    let properties = package.export_properties(default_object_index).unwrap();
+
+    let var_hard = properties
+        .iter()
+        .find(|property| property.name() == "VarHard")
+        .unwrap();
+    let PropertyValue::Object(var_hard) = var_hard.value else {
+        panic!("unexpected value for VarHard: {:?}", var_hard.value);
+    };
+    assert!(matches!(var_hard, ObjectReference::Import { .. }));
+    assert_eq!(
+        package.object_path(var_hard).unwrap(),
+        "/Game/SimpleRefs/SimpleRefsDefaultsRef.SimpleRefsDefaultsRef_C"
+    );
+
+    let var_soft = properties
+        .iter()
+        .find(|property| property.name() == "VarSoft")
+        .unwrap();
+    assert_eq!(
+        var_soft.value,
+        PropertyValue::SoftObject(SoftObjectPath {
+            package_name: "/Game/SimpleRefs/SimpleRefsSoftRef".to_string(),
+            asset_name: "SimpleRefsSoftRef_C".to_string(),
+            sub_path: String::new(),
+        })
+    );
+}
+
+#[apply(all_versions)]
+fn all_export_properties(#[case] version_info: UnrealVersionInfo) {
+    for asset in [
+        "/Game/SimpleRefs/SimpleRefsDefaultsRef",
+        "/Game/SimpleRefs/SimpleRefsGraphRef",
+        "/Game/SimpleRefs/SimpleRefsRoot",
+        "/Game/SimpleRefs/SimpleRefsSoftRef",
+    ] {
+        let asset_path = version_info.version.resolve_ue_path(asset);
+        let mut package = AssetHeader::new(File::open(asset_path).unwrap()).unwrap();
+        for export_index in 0..package.exports.len() {
+            let properties = package.export_properties(export_index);
+            assert!(
+                properties.is_ok(),
+                "failed to read properties of export {} in {}: {:?}",
+                export_index,
+                asset,
+                properties
+            );
+        }
+    }
+}
+
+#[apply(all_versions)]
+fn complete_type_names(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut package = AssetHeader::new(File::open(asset_path).unwrap()).unwrap();
+
+    let blueprint_index = package
+        .exports
+        .iter()
+        .position(|export| package.resolve_name(&export.object_name).unwrap() == "SimpleRefsRoot")
+        .unwrap();
+    let properties = package.export_properties(blueprint_index).unwrap();
+
+    let new_variables = properties
+        .iter()
+        .find(|property| property.name() == "NewVariables")
+        .unwrap();
+    if version_info.object_version_ue5 >= Some(ObjectVersionUE5::PROPERTY_TAG_COMPLETE_TYPE_NAME) {
+        assert_eq!(
+            new_variables
+                .tag
+                .complete_type_name
+                .as_ref()
+                .unwrap()
+                .to_string(),
+            "ArrayProperty(StructProperty(BPVariableDescription(/Script/Engine)))"
+        );
+    } else {
+        assert_eq!(new_variables.tag.complete_type_name, None);
+    }
+
+    let PropertyValue::Array(variables) = &new_variables.value else {
+        panic!(
+            "unexpected value for NewVariables: {:?}",
+            new_variables.value
+        );
+    };
+    assert!(!variables.is_empty());
+    if version_info.object_version < ObjectVersion::VER_UE4_INNER_ARRAY_TAG_INFO {
+        // Arrays of structs didn't record which struct they contain
+        return;
+    }
+    for variable in variables {
+        let PropertyValue::Struct { struct_name, .. } = variable else {
+            panic!("unexpected variable: {:?}", variable);
+        };
+        assert_eq!(struct_name, "BPVariableDescription");
+    }
+}
+
+/// Append an object with a single tagged property to the end of a package, and point its first export at it
+fn package_with_property(
+    version_info: &UnrealVersionInfo,
+    name: &str,
+    type_name: &str,
+    value: &[u8],
+) -> AssetHeader<Cursor<Vec<u8>>> {
+    let mut bytes = version_info
+        .version
+        .read_asset("/Game/SimpleRefs/SimpleRefsRoot");
+    let package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    let push_name = |data: &mut Vec<u8>, name: &str| {
+        push_i32(data, package.find_name(name).unwrap().index as i32);
+        push_i32(data, 0);
+    };
+
+    let has_tag_extensions = version_info.object_version_ue5
+        >= Some(ObjectVersionUE5::PROPERTY_TAG_EXTENSION_AND_OVERRIDABLE_SERIALIZATION);
+    let mut object = Vec::new();
+    if has_tag_extensions {
+        object.push(0);
+    }
+    push_name(&mut object, name);
+    if version_info.object_version_ue5 >= Some(ObjectVersionUE5::PROPERTY_TAG_COMPLETE_TYPE_NAME) {
+        // The type name is a single node without parameters, and none of the tag flags are set
+        push_name(&mut object, type_name);
+        push_i32(&mut object, 0);
+        push_i32(&mut object, value.len() as i32);
+        object.push(0);
+    } else {
+        push_name(&mut object, type_name);
+        push_i32(&mut object, value.len() as i32);
+        push_i32(&mut object, 0);
+        if version_info.object_version >= ObjectVersion::VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG {
+            object.push(0);
+        }
+        if has_tag_extensions {
+            object.push(0);
+        }
+    }
+    object.extend_from_slice(value);
+    push_name(&mut object, "None");
+
+    let object_offset = append_to_package(&mut bytes, &object);
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    package.exports[0].serial_offset = object_offset as i64;
+    package.exports[0].serial_size = object.len() as i64;
+    package
+}
+
+#[apply(all_versions)]
+fn culture_invariant_text(#[case] version_info: UnrealVersionInfo) {
+    // Culture invariant strings were added in 4.23, older versions only have the flags and history type
+    let has_culture_invariant_string = (version_info.version.0, version_info.version.1) >= (4, 23);
+    let mut value = Vec::new();
+    push_i32(&mut value, 0);
+    value.push(-1i8 as u8);
+    if has_culture_invariant_string {
+        push_i32(&mut value, 1);
+        push_string(&mut value, "Invariant");
+    }
+    let mut package =
+        package_with_property(&version_info, "PropertyTooltip", "TextProperty", &value);
+
+    let properties = package.export_properties(0).unwrap();
+    assert_eq!(properties.len(), 1);
+    assert_eq!(properties[0].name(), "PropertyTooltip");
+    assert_eq!(properties[0].tag.type_name, "TextProperty");
+    assert_eq!(
+        properties[0].value,
+        PropertyValue::Text(Text {
+            flags: 0,
+            namespace: None,
+            key: None,
+            source_string: has_culture_invariant_string.then(|| "Invariant".to_string()),
+        })
+    );
+}
+
+#[apply(all_versions)]
+fn invalid_values(#[case] version_info: UnrealVersionInfo) {
+    // A value that's larger than its type reads is kept with the error
+    let value = [1, 0, 0, 0, 2, 0, 0, 0];
+    let mut package =
+        package_with_property(&version_info, "PropertyTooltip", "IntProperty", &value);
+    let properties = package.export_properties(0).unwrap();
+    assert_eq!(
+        properties[0].value,
+        PropertyValue::Invalid {
+            bytes: value.to_vec(),
+            error: "value of PropertyTooltip used 4 bytes, but its tag has 8 bytes".to_string(),
+        }
+    );
+
+    // So is a value that fails to read, like a name that isn't in the name table
+    let mut value = Vec::new();
+    push_i32(&mut value, i32::MAX);
+    push_i32(&mut value, 0);
+    let mut package =
+        package_with_property(&version_info, "PropertyTooltip", "NameProperty", &value);
+    let properties = package.export_properties(0).unwrap();
+    let PropertyValue::Invalid { bytes, error } = &properties[0].value else {
+        panic!("unexpected value: {:?}", properties[0].value);
+    };
+    assert_eq!(bytes, &value);
+    assert!(!error.is_empty());
+}
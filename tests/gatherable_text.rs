@@ -0,0 +1,114 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, LocMetadataObject, LocMetadataValue};
+
+#[apply(all_versions)]
+fn gathered_text(#[case] version_info: UnrealVersionInfo) {
+    let mut bytes = version_info
+        .version
+        .read_asset("/Game/SimpleRefs/SimpleRefsRoot");
+
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    let texts = package.gatherable_text_data().unwrap();
+    assert_eq!(
+        texts.len(),
+        package.gatherable_text_data_count.max(0) as usize
+    );
+
+    // Only the oldest assets were saved with the text of the blueprint editor
+    if !texts.is_empty() {
+        let category = texts
+            .iter()
+            .find(|text| text.namespace == "KismetSchema" && text.source_string == "Default")
+            .unwrap();
+        assert_eq!(category.source_site_contexts.len(), 2);
+        for site in &category.source_site_contexts {
+            assert_eq!(site.key, "Default");
+            assert!(site.site_description.ends_with(".NewVariables.Category"));
+            assert!(site.is_editor_only);
+            assert!(!site.is_optional);
+        }
+        assert!(
+            texts
+                .iter()
+                .flat_map(|text| &text.source_site_contexts)
+                .all(|site| site
+                    .site_description
+                    .starts_with("/Game/SimpleRefs/SimpleRefsRoot."))
+        );
+    }
+
+    // Append a text with metadata to the end of the package
+    let mut text = Vec::new();
+    push_string(&mut text, "MyNamespace");
+    push_string(&mut text, "Hello \"world\"");
+    push_i32(&mut text, 2);
+    push_string(&mut text, "Gender");
+    let gender_type_offset = text.len();
+    push_i32(&mut text, 2);
+    push_string(&mut text, "Feminine");
+    push_string(&mut text, "Plurals");
+    push_i32(&mut text, 4);
+    push_i32(&mut text, 2);
+    push_i32(&mut text, 3);
+    push_i32(&mut text, 1);
+    push_i32(&mut text, 5);
+    push_i32(&mut text, 1);
+    push_string(&mut text, "Count");
+    push_i32(&mut text, 1);
+    push_i32(&mut text, 1);
+    push_string(&mut text, "MyKey");
+    push_string(&mut text, "/Game/MyPackage.MyObject.MyText");
+    push_i32(&mut text, 0);
+    push_i32(&mut text, 1);
+    push_i32(&mut text, 0);
+    push_i32(&mut text, 0);
+
+    let text_offset = append_to_package(&mut bytes, &text);
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    package.gatherable_text_data_offset = text_offset;
+    package.gatherable_text_data_count = 1;
+
+    let texts = package.gatherable_text_data().unwrap();
+    assert_eq!(texts.len(), 1);
+    assert_eq!(texts[0].namespace, "MyNamespace");
+    assert_eq!(texts[0].source_string, "Hello \"world\"");
+    let expected_metadata = LocMetadataObject::from([
+        (
+            "Gender".to_string(),
+            LocMetadataValue::String("Feminine".to_string()),
+        ),
+        (
+            "Plurals".to_string(),
+            LocMetadataValue::Array(vec![
+                LocMetadataValue::Boolean(true),
+                LocMetadataValue::Object(LocMetadataObject::from([(
+                    "Count".to_string(),
+                    LocMetadataValue::Null,
+                )])),
+            ]),
+        ),
+    ]);
+    assert_eq!(texts[0].source_string_metadata, expected_metadata);
+
+    let site = &texts[0].source_site_contexts[0];
+    assert_eq!(site.key, "MyKey");
+    assert_eq!(site.site_description, "/Game/MyPackage.MyObject.MyText");
+    assert!(!site.is_editor_only);
+    assert!(site.is_optional);
+    assert!(site.info_metadata.is_empty());
+    assert!(site.key_metadata.is_empty());
+
+    // Metadata of an unknown type can't be skipped
+    let mut bytes = package.archive.reader.into_inner();
+    let gender_type_offset = text_offset as usize + gender_type_offset;
+    bytes[gender_type_offset..gender_type_offset + 4].copy_from_slice(&9i32.to_le_bytes());
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    package.gatherable_text_data_offset = text_offset;
+    package.gatherable_text_data_count = 1;
+    assert!(package.gatherable_text_data().is_err());
+}
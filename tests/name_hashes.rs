@@ -0,0 +1,105 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, Error, NameHashes};
+
+#[apply(all_versions)]
+fn name_hashes(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut bytes = std::fs::read(&asset_path).unwrap();
+
+    let package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    package.verify_name_hashes().unwrap();
+    if version_info.object_version < ObjectVersion::VER_UE4_NAME_HASHES_SERIALIZED {
+        assert!(package.name_hashes.is_empty());
+        return;
+    }
+
+    assert_eq!(package.name_hashes.len(), package.names.len());
+    for (name, hashes) in package.names.iter().zip(&package.name_hashes) {
+        assert_eq!(*hashes, NameHashes::new(name));
+    }
+    let var_soft = package.find_name("VarSoft").unwrap().index as usize;
+    assert_eq!(
+        package.name_hashes[var_soft],
+        NameHashes {
+            non_case_preserving: 0xFBBA,
+            case_preserving: 0xB273,
+        }
+    );
+
+    // Corrupt the case preserving hash of a name
+    let entry = b"VarSoft\0";
+    let hash_position = bytes
+        .windows(entry.len())
+        .position(|window| window == entry)
+        .unwrap()
+        + entry.len()
+        + 2;
+    bytes[hash_position] ^= 0xFF;
+    let package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    assert_eq!(package.names[var_soft], "VarSoft");
+    assert_eq!(package.name_hashes[var_soft].case_preserving, 0xB28C);
+    assert!(matches!(
+        package.verify_name_hashes(),
+        Err(Error::InvalidNameHash(_))
+    ));
+}
+
+#[test]
+fn name_hashes_case() {
+    let hashes = NameHashes::new("VarSoft");
+    let uppercase_hashes = NameHashes::new("VARSOFT");
+    assert_eq!(
+        hashes.non_case_preserving,
+        uppercase_hashes.non_case_preserving
+    );
+    assert_ne!(hashes.case_preserving, uppercase_hashes.case_preserving);
+
+    // Non-ASCII names are hashed as UTF-16, and only ASCII characters are case insensitive
+    let wide_hashes = NameHashes::new("VarSøft");
+    assert_ne!(wide_hashes, hashes);
+    assert_eq!(
+        wide_hashes.non_case_preserving,
+        NameHashes::new("varsøft").non_case_preserving
+    );
+    assert_ne!(
+        wide_hashes.non_case_preserving,
+        NameHashes::new("VARSØFT").non_case_preserving
+    );
+}
+
+#[apply(all_versions)]
+fn name_lookup(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let package = AssetHeader::new(std::io::BufReader::new(
+        std::fs::File::open(&asset_path).unwrap(),
+    ))
+    .unwrap();
+
+    let lookup = package.name_lookup();
+    for name in &package.names {
+        for name in [name.clone(), name.to_uppercase(), name.to_lowercase()] {
+            let first_index = package
+                .names
+                .iter()
+                .position(|candidate| candidate.to_lowercase() == name.to_lowercase())
+                .unwrap();
+            assert_eq!(lookup.find_name(&name).unwrap().index as usize, first_index);
+            assert_eq!(package.find_name(&name), lookup.find_name(&name));
+        }
+    }
+    assert_eq!(
+        lookup.find_name("/Script/CoreUObject").unwrap().number,
+        None
+    );
+    assert_eq!(lookup.find_name("NotAName"), None);
+    assert_eq!(package.find_name("NotAName"), None);
+}
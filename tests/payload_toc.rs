@@ -0,0 +1,139 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, IoHash, PayloadAccessMode, PayloadTocEntry};
+
+/// Serialize a package trailer the way `FPackageTrailerBuilder::BuildAndAppendTrailer` does, with `PAYLOAD` as the data of
+/// every local payload. Offsets of local payloads are filled in.
+pub fn package_trailer(version: u32, entries: &[PayloadTocEntry]) -> Vec<u8> {
+    let header_length = if version >= 1 {
+        28 + 49 * entries.len()
+    } else {
+        29 + 44 * entries.len()
+    };
+    let local_count = entries.iter().filter(|entry| entry.is_local()).count();
+
+    let mut trailer = Vec::new();
+    trailer.extend_from_slice(&0xD1C43B2E80A5F697u64.to_le_bytes());
+    trailer.extend_from_slice(&version.to_le_bytes());
+    trailer.extend_from_slice(&(header_length as u32).to_le_bytes());
+    trailer.extend_from_slice(&((local_count * PAYLOAD.len()) as u64).to_le_bytes());
+    if version == 0 {
+        trailer.push(entries[0].access_mode as u8);
+    }
+    trailer.extend_from_slice(&(entries.len() as i32).to_le_bytes());
+
+    let mut local_offset = header_length as i64;
+    for entry in entries {
+        trailer.extend_from_slice(&entry.identifier.0);
+        if entry.is_local() {
+            trailer.extend_from_slice(&local_offset.to_le_bytes());
+            local_offset += PAYLOAD.len() as i64;
+        } else {
+            trailer.extend_from_slice(&(-1i64).to_le_bytes());
+        }
+        trailer.extend_from_slice(&entry.compressed_size.to_le_bytes());
+        trailer.extend_from_slice(&entry.raw_size.to_le_bytes());
+        if version >= 2 {
+            trailer.extend_from_slice(&entry.flags.to_le_bytes());
+            trailer.extend_from_slice(&entry.filter_flags.to_le_bytes());
+        }
+        if version >= 1 {
+            trailer.push(entry.access_mode as u8);
+        }
+    }
+    assert_eq!(trailer.len(), header_length);
+
+    for _ in 0..local_count {
+        trailer.extend_from_slice(PAYLOAD);
+    }
+
+    let trailer_length = trailer.len() as u64 + 20;
+    trailer.extend_from_slice(&0x29BFCA045138DE76u64.to_le_bytes());
+    trailer.extend_from_slice(&trailer_length.to_le_bytes());
+    trailer.extend_from_slice(&0x9E2A83C1u32.to_le_bytes());
+    trailer
+}
+
+fn payload(identifier: u8, access_mode: PayloadAccessMode) -> PayloadTocEntry {
+    PayloadTocEntry {
+        identifier: IoHash([identifier; 20]),
+        offset_in_file: None,
+        compressed_size: PAYLOAD.len() as u64,
+        raw_size: 4096,
+        flags: 0,
+        filter_flags: 0,
+        access_mode,
+    }
+}
+
+#[apply(all_versions)]
+fn payload_table_of_contents(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut bytes = std::fs::read(&asset_path).unwrap();
+
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    let payload_toc = package.payload_toc().unwrap();
+    assert!(payload_toc.entries.is_empty());
+    if package.payload_toc_offset <= 0 {
+        return;
+    }
+    assert_eq!(payload_toc.version, 2);
+
+    // Replace the trailer at the end of the package with one that has payloads
+    let payload_toc_offset = package.payload_toc_offset as usize;
+    let mut virtualized = payload(2, PayloadAccessMode::Virtualized);
+    virtualized.filter_flags = 0x4;
+    let entries = [
+        payload(1, PayloadAccessMode::Local),
+        virtualized,
+        payload(3, PayloadAccessMode::Local),
+    ];
+    bytes.truncate(payload_toc_offset);
+    bytes.extend_from_slice(&package_trailer(2, &entries));
+
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    let payload_toc = package.payload_toc().unwrap();
+    assert_eq!(payload_toc.payloads_data_length, 2 * PAYLOAD.len() as u64);
+    assert_eq!(payload_toc.entries.len(), 3);
+    assert_eq!(payload_toc.local_payloads().count(), 2);
+    assert_eq!(payload_toc.virtualized_payloads().count(), 1);
+    assert_eq!(
+        payload_toc.entries[1],
+        PayloadTocEntry {
+            offset_in_file: None,
+            ..virtualized
+        }
+    );
+    assert_eq!(
+        payload_toc.entries[0].identifier.to_string(),
+        "01".repeat(20)
+    );
+
+    // The offsets of local payloads point at their data
+    for entry in payload_toc.local_payloads() {
+        let offset = entry.offset_in_file.unwrap() as usize;
+        assert_eq!(&bytes[offset..offset + PAYLOAD.len()], PAYLOAD);
+    }
+
+    // The initial version of the table has one access mode for all payloads
+    let entries = [
+        payload(4, PayloadAccessMode::Virtualized),
+        payload(5, PayloadAccessMode::Virtualized),
+    ];
+    bytes.truncate(payload_toc_offset);
+    bytes.extend_from_slice(&package_trailer(0, &entries));
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    let payload_toc = package.payload_toc().unwrap();
+    assert_eq!(payload_toc.version, 0);
+    assert_eq!(payload_toc.entries, entries);
+
+    bytes[payload_toc_offset] ^= 0xff;
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    assert!(package.payload_toc().is_err());
+}
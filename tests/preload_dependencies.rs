@@ -0,0 +1,66 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, ObjectReference, PreloadDependencies};
+
+#[apply(all_versions)]
+fn preload_dependencies(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut bytes = std::fs::read(&asset_path).unwrap();
+
+    // Preload dependencies are only serialized when cooking
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    for export_index in 0..package.exports.len() {
+        assert_eq!(
+            package.export_preload_dependencies(export_index).unwrap(),
+            PreloadDependencies::default()
+        );
+    }
+    assert!(
+        package
+            .export_preload_dependencies(package.exports.len())
+            .is_err()
+    );
+
+    // Append a table to the end of the package, where the dependencies of the first export start after one unrelated entry
+    let table = [1, -1, 2, -2, 0, -3, 3];
+    let table_offset = bytes.len() as i32;
+    for index in table {
+        bytes.extend_from_slice(&i32::to_le_bytes(index));
+    }
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    package.preload_dependency_offset = table_offset;
+    package.preload_dependency_count = table.len() as i32;
+    let export = &mut package.exports[0];
+    export.first_export_dependency = 1;
+    export.serialization_before_serialization_dependencies = 2;
+    export.create_before_serialization_dependencies = 0;
+    export.serialization_before_create_dependencies = 1;
+    export.create_before_create_dependencies = 3;
+
+    assert_eq!(
+        package.export_preload_dependencies(0).unwrap(),
+        PreloadDependencies {
+            serialization_before_serialization: vec![
+                ObjectReference::Import { import_index: 0 },
+                ObjectReference::Export { export_index: 1 },
+            ],
+            create_before_serialization: vec![],
+            serialization_before_create: vec![ObjectReference::Import { import_index: 1 }],
+            create_before_create: vec![
+                ObjectReference::None,
+                ObjectReference::Import { import_index: 2 },
+                ObjectReference::Export { export_index: 2 },
+            ],
+        }
+    );
+
+    // Dependencies past the end of the table are an error
+    package.exports[0].create_before_create_dependencies = 4;
+    assert!(package.export_preload_dependencies(0).is_err());
+}
@@ -0,0 +1,52 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, ObjectReference};
+
+#[apply(all_versions)]
+fn searchable_names(#[case] version_info: UnrealVersionInfo) {
+    let mut bytes = version_info
+        .version
+        .read_asset("/Game/SimpleRefs/SimpleRefsRoot");
+
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    assert!(package.searchable_names().unwrap().is_empty());
+    if version_info.object_version < ObjectVersion::VER_UE4_ADDED_SEARCHABLE_NAMES {
+        assert_eq!(package.searchable_names_offset, None);
+        return;
+    }
+
+    // Append a map to the end of the package, with names from the name table
+    let var_soft = package.find_name("VarSoft").unwrap().index as i32;
+    let var_hard = package.find_name("VarHard").unwrap().index as i32;
+    let mut map = Vec::new();
+    push_i32(&mut map, 2);
+    push_i32(&mut map, 1);
+    push_i32(&mut map, 2);
+    for (index, number) in [(var_soft, 0), (var_soft, 3)] {
+        push_i32(&mut map, index);
+        push_i32(&mut map, number);
+    }
+    push_i32(&mut map, -1);
+    push_i32(&mut map, 1);
+    push_i32(&mut map, var_hard);
+    push_i32(&mut map, 0);
+
+    let map_offset = append_to_package(&mut bytes, &map);
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    package.searchable_names_offset = Some(map_offset);
+
+    let searchable_names = package.searchable_names().unwrap();
+    assert_eq!(searchable_names.len(), 2);
+    assert_eq!(
+        searchable_names[&ObjectReference::Export { export_index: 0 }],
+        ["VarSoft", "VarSoft_2"]
+    );
+    assert_eq!(
+        searchable_names[&ObjectReference::Import { import_index: 0 }],
+        ["VarHard"]
+    );
+}
@@ -0,0 +1,81 @@
+use std::{fs::File, io::Cursor};
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, AssetPackage};
+
+/// Split an editor asset into a header and export data, the way cooked assets are split into a `.uasset` and `.uexp`
+fn split_asset(bytes: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let header = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    let total_header_size = header.total_header_size as usize;
+    (
+        bytes[..total_header_size].to_vec(),
+        bytes[total_header_size..].to_vec(),
+    )
+}
+
+#[apply(all_versions)]
+fn split_export_data(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let bytes = std::fs::read(asset_path).unwrap();
+
+    let mut header = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    let (header_bytes, export_bytes) = split_asset(&bytes);
+    let mut package =
+        AssetPackage::new(Cursor::new(header_bytes), Some(Cursor::new(export_bytes))).unwrap();
+    assert!(package.has_split_exports());
+
+    assert_eq!(package.names, header.names);
+    assert_eq!(package.exports.len(), header.exports.len());
+    for export_index in 0..header.exports.len() {
+        assert_eq!(
+            package.export_data(export_index).unwrap(),
+            header.export_data(export_index).unwrap()
+        );
+        assert_eq!(
+            package.export_properties(export_index).unwrap(),
+            header.export_properties(export_index).unwrap()
+        );
+    }
+}
+
+#[apply(all_versions)]
+fn open_package(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+
+    let mut package = AssetPackage::open(&asset_path).unwrap();
+    assert!(!package.has_split_exports());
+    let last_export_index = package.exports.len() - 1;
+    let expected_data = package.export_data(last_export_index).unwrap();
+
+    let split_directory = std::env::temp_dir().join(format!(
+        "uasset-split-packages-{}.{}-{}",
+        version_info.version.0,
+        version_info.version.1,
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&split_directory).unwrap();
+    let (header_bytes, export_bytes) = split_asset(&std::fs::read(&asset_path).unwrap());
+    let split_asset_path = split_directory.join("SimpleRefsRoot.uasset");
+    std::fs::write(&split_asset_path, header_bytes).unwrap();
+    std::fs::write(split_directory.join("SimpleRefsRoot.uexp"), export_bytes).unwrap();
+
+    let mut split_package = AssetPackage::open(&split_asset_path).unwrap();
+    assert!(split_package.has_split_exports());
+    assert_eq!(
+        split_package.export_data(last_export_index).unwrap(),
+        expected_data
+    );
+
+    // Without the `.uexp` the export data is missing
+    let mut header = AssetHeader::new(File::open(&split_asset_path).unwrap()).unwrap();
+    assert!(header.export_data(last_export_index).is_err());
+
+    std::fs::remove_dir_all(split_directory).unwrap();
+}
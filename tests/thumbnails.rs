@@ -0,0 +1,84 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, ThumbnailFormat, ThumbnailInfo};
+
+const PNG_DATA: &[u8] = b"\x89PNG\r\n\x1a\nnot really a png";
+const JPEG_DATA: &[u8] = b"\xff\xd8\xff\xe0not really a jpeg";
+
+/// Serialize a thumbnail the way `FObjectThumbnail::Serialize` does
+pub fn object_thumbnail(width: i32, height: i32, compressed_image_data: &[u8]) -> Vec<u8> {
+    let mut thumbnail = Vec::new();
+    thumbnail.extend_from_slice(&width.to_le_bytes());
+    thumbnail.extend_from_slice(&height.to_le_bytes());
+    thumbnail.extend_from_slice(&(compressed_image_data.len() as i32).to_le_bytes());
+    thumbnail.extend_from_slice(compressed_image_data);
+    thumbnail
+}
+
+/// Find where the file offset of the first entry in the thumbnail table is stored, which is after the count & two strings
+fn first_file_offset_position(
+    thumbnail_table_offset: i32,
+    thumbnail_info: &ThumbnailInfo,
+) -> usize {
+    thumbnail_table_offset as usize
+        + 4
+        + (4 + thumbnail_info.object_class_name.len() + 1)
+        + (4 + thumbnail_info.object_path_without_package_name.len() + 1)
+}
+
+fn read_thumbnail_infos(package: &mut AssetHeader<Cursor<Vec<u8>>>) -> Vec<ThumbnailInfo> {
+    package
+        .thumbnail_iter()
+        .unwrap()
+        .collect::<uasset::Result<Vec<_>>>()
+        .unwrap()
+}
+
+#[apply(all_versions)]
+fn thumbnail_images(#[case] version_info: UnrealVersionInfo) {
+    let asset_path = version_info
+        .version
+        .resolve_ue_path("/Game/SimpleRefs/SimpleRefsRoot");
+    let mut bytes = std::fs::read(&asset_path).unwrap();
+
+    // Blueprints without any components don't get a rendered thumbnail
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    let thumbnail_infos = read_thumbnail_infos(&mut package);
+    assert_eq!(thumbnail_infos[0].object_class_name, "Blueprint");
+    for thumbnail_info in &thumbnail_infos {
+        assert!(package.read_thumbnail(thumbnail_info).unwrap().is_empty());
+    }
+
+    // Append images to the end of the package, and point the first thumbnail at them
+    let file_offset_position =
+        first_file_offset_position(package.thumbnail_table_offset, &thumbnail_infos[0]);
+    let png_offset = bytes.len() as i32;
+    bytes.extend_from_slice(&object_thumbnail(256, 128, PNG_DATA));
+    let jpeg_offset = bytes.len() as i32;
+    bytes.extend_from_slice(&object_thumbnail(64, -32, JPEG_DATA));
+
+    bytes[file_offset_position..file_offset_position + 4]
+        .copy_from_slice(&png_offset.to_le_bytes());
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    let thumbnail_info = read_thumbnail_infos(&mut package).remove(0);
+    assert_eq!(thumbnail_info.file_offset, png_offset);
+    let thumbnail = package.read_thumbnail(&thumbnail_info).unwrap();
+    assert!(!thumbnail.is_empty());
+    assert_eq!((thumbnail.width, thumbnail.height), (256, 128));
+    assert_eq!(thumbnail.format, ThumbnailFormat::Png);
+    assert_eq!(thumbnail.compressed_image_data, PNG_DATA);
+
+    bytes[file_offset_position..file_offset_position + 4]
+        .copy_from_slice(&jpeg_offset.to_le_bytes());
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    let thumbnail_info = read_thumbnail_infos(&mut package).remove(0);
+    let thumbnail = package.read_thumbnail(&thumbnail_info).unwrap();
+    assert_eq!((thumbnail.width, thumbnail.height), (64, 32));
+    assert_eq!(thumbnail.format, ThumbnailFormat::Jpeg);
+    assert_eq!(thumbnail.format.extension(), "jpg");
+    assert_eq!(thumbnail.compressed_image_data, JPEG_DATA);
+}
@@ -0,0 +1,314 @@
+use std::io::Cursor;
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{
+    AssetHeader, ObjectReference, PackageFlags, PropertySchema, PropertySchemas, PropertyTypeName,
+    PropertyValue, StructSchema,
+};
+
+fn property(name: &str, type_name: &str, array_dim: u32) -> PropertySchema {
+    PropertySchema {
+        name: name.to_string(),
+        type_name: type_name.parse().unwrap(),
+        array_dim,
+    }
+}
+
+fn test_schemas() -> PropertySchemas {
+    let mut schemas = PropertySchemas::new();
+    schemas.add_struct("Object".to_string(), StructSchema::default());
+    schemas.add_struct(
+        "SimpleRefsRoot_C".to_string(),
+        StructSchema {
+            super_struct: Some("Object".to_string()),
+            properties: vec![
+                property(
+                    "UberGraphFrame",
+                    "StructProperty(PointerToUberGraphFrame(/Script/Engine))",
+                    1,
+                ),
+                property("VarHard", "ObjectProperty", 1),
+                property("Health", "FloatProperty", 1),
+                property("Counts", "IntProperty", 2),
+                property("Mode", "ByteProperty(EMode(/Game/SimpleRefs/EMode))", 1),
+                property(
+                    "Tint",
+                    "StructProperty(LinearColor(/Script/CoreUObject))",
+                    1,
+                ),
+                property("Tags", "ArrayProperty(NameProperty)", 1),
+                property("Unused", "IntProperty", 1),
+                property("Ids", "SetProperty(IntProperty)", 1),
+            ],
+        },
+    );
+    schemas.add_enum(
+        "EMode".to_string(),
+        [
+            ("EMode::First".to_string(), 0),
+            ("EMode::Second".to_string(), 1),
+        ],
+    );
+    schemas
+}
+
+/// Find where the package flags are stored in the summary, which is right after the total header size and the package name
+fn package_flags_offset(
+    bytes: &[u8],
+    total_header_size: i32,
+    package_flags: PackageFlags,
+) -> usize {
+    let total_header_size = total_header_size.to_le_bytes();
+    let package_flags = package_flags.bits().to_le_bytes();
+    (0..bytes.len() - 8)
+        .find(|&offset| {
+            if bytes[offset..offset + 4] != total_header_size {
+                return false;
+            }
+            let name_length = i32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap());
+            if !(1..1024).contains(&name_length) {
+                return false;
+            }
+            let flags_offset = offset + 8 + name_length as usize;
+            bytes.get(flags_offset..flags_offset + 4) == Some(package_flags.as_slice())
+        })
+        .map(|offset| {
+            offset
+                + 8
+                + i32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()) as usize
+        })
+        .unwrap()
+}
+
+/// Rewrite the properties of the CDO of `SimpleRefsRoot` as the unversioned properties that `build_properties` serializes, and
+/// return the patched package and the index of the CDO
+fn unversioned_default_object_package(
+    version_info: &UnrealVersionInfo,
+    build_properties: impl FnOnce(&AssetHeader<Cursor<&[u8]>>, &mut Vec<u8>),
+) -> (AssetHeader<Cursor<Vec<u8>>>, usize) {
+    let mut bytes = version_info
+        .version
+        .read_asset("/Game/SimpleRefs/SimpleRefsRoot");
+
+    let package = AssetHeader::new(Cursor::new(bytes.as_slice())).unwrap();
+    let default_object_index = package
+        .exports
+        .iter()
+        .position(|export| {
+            package.resolve_name(&export.object_name).unwrap() == "Default__SimpleRefsRoot_C"
+        })
+        .unwrap();
+    let default_object = &package.exports[default_object_index];
+
+    let mut data = Vec::new();
+    if version_info.object_version_ue5
+        >= Some(ObjectVersionUE5::PROPERTY_TAG_EXTENSION_AND_OVERRIDABLE_SERIALIZATION)
+    {
+        data.push(0u8);
+    }
+    build_properties(&package, &mut data);
+    assert!(data.len() <= default_object.serial_size as usize);
+
+    let serial_offset = default_object.serial_offset as usize;
+    let flags_offset =
+        package_flags_offset(&bytes, package.total_header_size, package.package_flags);
+    let package_flags = package.package_flags | PackageFlags::UnversionedProperties;
+    drop(package);
+    bytes[serial_offset..serial_offset + data.len()].copy_from_slice(&data);
+    bytes[flags_offset..flags_offset + 4].copy_from_slice(&package_flags.bits().to_le_bytes());
+
+    (
+        AssetHeader::new(Cursor::new(bytes)).unwrap(),
+        default_object_index,
+    )
+}
+
+#[apply(all_versions)]
+fn unversioned_default_object(#[case] version_info: UnrealVersionInfo) {
+    let (mut package, default_object_index) =
+        unversioned_default_object_package(&version_info, |package, data| {
+            let tag_name_index = package
+                .names
+                .iter()
+                .position(|name| name == "VarSoft")
+                .unwrap() as u32;
+
+            // Skip UberGraphFrame, then VarHard, Health (which is zero) & both elements of Counts
+            data.extend_from_slice(&(1u16 | 0x80 | (4 << 9)).to_le_bytes());
+            // Then Mode, Tint & Tags, which is the last fragment
+            data.extend_from_slice(&(0x100u16 | (3 << 9)).to_le_bytes());
+            data.push(0b0010);
+            data.extend_from_slice(&(-1i32).to_le_bytes());
+            data.extend_from_slice(&7i32.to_le_bytes());
+            data.extend_from_slice(&8i32.to_le_bytes());
+            data.push(1);
+            for component in [1.0f32, 0.5, 0.25, 1.0] {
+                data.extend_from_slice(&component.to_le_bytes());
+            }
+            data.extend_from_slice(&1i32.to_le_bytes());
+            data.extend_from_slice(&tag_name_index.to_le_bytes());
+            data.extend_from_slice(&0u32.to_le_bytes());
+        });
+
+    assert!(
+        package
+            .package_flags
+            .contains(PackageFlags::UnversionedProperties)
+    );
+    assert!(package.export_properties(default_object_index).is_err());
+
+    let schemas = test_schemas();
+    let properties = package
+        .export_properties_with_schemas(default_object_index, &schemas)
+        .unwrap();
+    let summary: Vec<(&str, i32, &PropertyValue)> = properties
+        .iter()
+        .map(|property| (property.name(), property.tag.array_index, &property.value))
+        .collect();
+
+    assert_eq!(summary.len(), 7, "unexpected properties: {:?}", summary);
+    assert_eq!(
+        summary[0],
+        (
+            "VarHard",
+            0,
+            &PropertyValue::Object(ObjectReference::Import { import_index: 0 })
+        )
+    );
+    assert_eq!(summary[1], ("Health", 0, &PropertyValue::Zero));
+    assert_eq!(summary[2], ("Counts", 0, &PropertyValue::Int(7)));
+    assert_eq!(summary[3], ("Counts", 1, &PropertyValue::Int(8)));
+    assert_eq!(
+        summary[4],
+        ("Mode", 0, &PropertyValue::Enum("EMode::Second".to_string()))
+    );
+    let PropertyValue::Struct {
+        struct_name,
+        properties: tint_properties,
+    } = summary[5].2
+    else {
+        panic!("unexpected value for Tint: {:?}", summary[5].2);
+    };
+    assert_eq!(summary[5].0, "Tint");
+    assert_eq!(struct_name, "LinearColor");
+    assert_eq!(
+        tint_properties
+            .iter()
+            .map(|property| (property.name(), &property.value))
+            .collect::<Vec<_>>(),
+        [
+            ("R", &PropertyValue::Float(1.0)),
+            ("G", &PropertyValue::Float(0.5)),
+            ("B", &PropertyValue::Float(0.25)),
+            ("A", &PropertyValue::Float(1.0)),
+        ]
+    );
+    assert_eq!(
+        summary[6],
+        (
+            "Tags",
+            0,
+            &PropertyValue::Array(vec![PropertyValue::Name("VarSoft".to_string())])
+        )
+    );
+}
+
+#[test]
+fn property_type_names() {
+    let type_name: PropertyTypeName =
+        "MapProperty(NameProperty, StructProperty(Vector(/Script/CoreUObject)))"
+            .parse()
+            .unwrap();
+    assert_eq!(type_name.name, "MapProperty");
+    assert_eq!(type_name.parameters.len(), 2);
+    assert_eq!(type_name.parameters[1].parameters[0].name, "Vector");
+    assert_eq!(
+        type_name.to_string(),
+        "MapProperty(NameProperty,StructProperty(Vector(/Script/CoreUObject)))"
+    );
+
+    assert!("".parse::<PropertyTypeName>().is_err());
+    assert!(
+        "ArrayProperty(IntProperty"
+            .parse::<PropertyTypeName>()
+            .is_err()
+    );
+    assert!("ArrayProperty()".parse::<PropertyTypeName>().is_err());
+}
+
+#[cfg(feature = "json-schemas")]
+#[test]
+fn json_schemas() {
+    use uasset::PropertySchemaProvider;
+
+    let json = r#"{
+        "structs": {
+            "MyActor": {
+                "super": "Actor",
+                "properties": [
+                    { "name": "Health", "type": "FloatProperty" },
+                    { "name": "Slots", "type": "ObjectProperty", "array_dim": 4 }
+                ]
+            }
+        },
+        "enums": {
+            "EMyEnum": { "EMyEnum::First": 0, "EMyEnum::Second": 1 }
+        }
+    }"#;
+
+    let mut schemas = PropertySchemas::new();
+    schemas.load_json(json.as_bytes()).unwrap();
+    assert_eq!(
+        schemas.struct_schema("MyActor"),
+        Some(&StructSchema {
+            super_struct: Some("Actor".to_string()),
+            properties: vec![
+                property("Health", "FloatProperty", 1),
+                property("Slots", "ObjectProperty", 4),
+            ],
+        })
+    );
+    assert_eq!(schemas.struct_schema("Actor"), None);
+    assert_eq!(
+        schemas.enum_value_name("EMyEnum", 1),
+        Some("EMyEnum::Second")
+    );
+    assert_eq!(schemas.enum_value_name("EMyEnum", 2), None);
+
+    let invalid_type = r#"{ "structs": { "MyActor": { "properties": [ { "name": "Health", "type": "Float(" } ] } } }"#;
+    assert!(
+        PropertySchemas::new()
+            .load_json(invalid_type.as_bytes())
+            .is_err()
+    );
+}
+
+#[apply(all_versions)]
+fn unversioned_set_with_removed_elements(#[case] version_info: UnrealVersionInfo) {
+    let (mut package, default_object_index) =
+        unversioned_default_object_package(&version_info, |_, data| {
+            // Skip to Ids, which is the only fragment
+            data.extend_from_slice(&(9u16 | 0x100 | (1 << 9)).to_le_bytes());
+            // Two elements that were removed from the default value, followed by the elements that were added
+            for value in [2i32, 10, 20, 3, 30, 40, 50] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        });
+
+    let properties = package
+        .export_properties_with_schemas(default_object_index, &test_schemas())
+        .unwrap();
+    assert_eq!(properties.len(), 1);
+    assert_eq!(properties[0].name(), "Ids");
+    assert_eq!(
+        properties[0].value,
+        PropertyValue::Set(vec![
+            PropertyValue::Int(30),
+            PropertyValue::Int(40),
+            PropertyValue::Int(50),
+        ])
+    );
+}
@@ -0,0 +1,142 @@
+use std::{collections::HashMap, io::Cursor};
+
+use ::rstest_reuse::*;
+use rstest::rstest;
+use test_utilities::*;
+
+use uasset::{AssetHeader, IntVector, WorldTileInfo, WorldTileLodInfo};
+
+fn push_vector(bytes: &mut Vec<u8>, large_world_coordinates: bool, vector: [f64; 3]) {
+    for component in vector {
+        if large_world_coordinates {
+            bytes.extend_from_slice(&component.to_le_bytes());
+        } else {
+            bytes.extend_from_slice(&(component as f32).to_le_bytes());
+        }
+    }
+}
+
+fn tile(position: [i32; 3], parent_tile_package_name: &str) -> WorldTileInfo {
+    WorldTileInfo {
+        position: IntVector {
+            x: position[0],
+            y: position[1],
+            z: position[2],
+        },
+        parent_tile_package_name: parent_tile_package_name.to_string(),
+        ..WorldTileInfo::default()
+    }
+}
+
+#[apply(all_versions)]
+fn world_tile_info(#[case] version_info: UnrealVersionInfo) {
+    let mut bytes = version_info
+        .version
+        .read_asset("/Game/SimpleRefs/SimpleRefsRoot");
+
+    // Only maps in a world composition have tile info
+    let mut package = AssetHeader::new(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(package.world_tile_info_data_offset, None);
+    assert_eq!(package.world_tile_info().unwrap(), None);
+
+    // Append tile info to the end of the package
+    let large_world_coordinates =
+        version_info.object_version_ue5 >= Some(ObjectVersionUE5::LARGE_WORLD_COORDINATES);
+    let mut tile_info = Vec::new();
+    for coordinate in [102400, -51200, 0] {
+        push_i32(&mut tile_info, coordinate);
+    }
+    push_vector(
+        &mut tile_info,
+        large_world_coordinates,
+        [-0.5, -1024.0, -256.0],
+    );
+    push_vector(
+        &mut tile_info,
+        large_world_coordinates,
+        [1024.0, 2048.25, 512.0],
+    );
+    tile_info.push(1);
+    push_string(&mut tile_info, "Mountains");
+    for reserved in [0, 0, 0] {
+        push_i32(&mut tile_info, reserved);
+    }
+    push_i32(&mut tile_info, 60000);
+    push_i32(&mut tile_info, 1);
+    push_i32(&mut tile_info, 1);
+    push_string(&mut tile_info, "/Game/Maps/Persistent");
+    push_i32(&mut tile_info, 2);
+    for relative_streaming_distance in [10000, 25000] {
+        push_i32(&mut tile_info, relative_streaming_distance);
+        tile_info.extend_from_slice(&[0; 16]);
+    }
+    push_i32(&mut tile_info, -3);
+
+    let tile_info_offset = append_to_package(&mut bytes, &tile_info);
+    let mut package = AssetHeader::new(Cursor::new(bytes)).unwrap();
+    package.world_tile_info_data_offset = Some(tile_info_offset);
+
+    let tile_info = package.world_tile_info().unwrap().unwrap();
+    assert_eq!(
+        tile_info.position,
+        IntVector {
+            x: 102400,
+            y: -51200,
+            z: 0
+        }
+    );
+    assert!(tile_info.bounds.is_valid);
+    assert_eq!(tile_info.bounds.min.x, -0.5);
+    assert_eq!(tile_info.bounds.max.y, 2048.25);
+    assert_eq!(tile_info.bounds.max.z, 512.0);
+    assert_eq!(tile_info.layer.name, "Mountains");
+    assert_eq!(tile_info.layer.streaming_distance, 60000);
+    assert!(tile_info.layer.distance_streaming_enabled);
+    assert!(tile_info.hide_in_tile_view);
+    assert_eq!(tile_info.parent_tile_package_name, "/Game/Maps/Persistent");
+    assert_eq!(
+        tile_info.lod_list,
+        [
+            WorldTileLodInfo {
+                relative_streaming_distance: 10000
+            },
+            WorldTileLodInfo {
+                relative_streaming_distance: 25000
+            }
+        ]
+    );
+    assert_eq!(tile_info.z_order, -3);
+}
+
+#[test]
+fn absolute_position() {
+    let tiles = HashMap::from([
+        ("/Game/Persistent", tile([1, 2, 3], "")),
+        ("/Game/Region", tile([10, 20, 30], "/Game/Persistent")),
+        ("/Game/Loop", tile([1000, 0, 0], "/Game/Loop")),
+    ]);
+    let find_tile = |package_name: &str| tiles.get(package_name);
+
+    let tile_info = tile([100, 200, 300], "/Game/Region");
+    assert_eq!(
+        tile_info.absolute_position(find_tile),
+        IntVector {
+            x: 111,
+            y: 222,
+            z: 333
+        }
+    );
+    let orphan = tile([5, 5, 5], "/Game/Missing");
+    assert_eq!(
+        orphan.absolute_position(find_tile),
+        IntVector { x: 5, y: 5, z: 5 }
+    );
+    assert_eq!(
+        tiles["/Game/Loop"].absolute_position(find_tile),
+        IntVector {
+            x: 2000,
+            y: 0,
+            z: 0
+        }
+    );
+}